@@ -0,0 +1,33 @@
+/// A path a field or variant is parsed from, either its canonical path or one of its
+/// `#[remote(alias = "..")]`es.
+pub(crate) struct ParsedPath {
+    /// index of the field or variant in the list passed to `parsed_paths`
+    pub(crate) index: usize,
+    pub(crate) path: String,
+    pub(crate) alias: bool,
+}
+
+/// Paths parsed for a list of fields or variants with the canonical `paths` and `aliases`, which
+/// are given without their `prefix` (`.` or `::`). Canonical paths come first, followed by the
+/// aliases.
+pub(crate) fn parsed_paths(paths: &[String], aliases: &[&[String]], prefix: &str) -> Vec<ParsedPath> {
+    let mut parsed: Vec<_> = paths.iter().enumerate().map(|(index, path)| {
+        ParsedPath { index, path: path.clone(), alias: false }
+    }).collect();
+    for (index, aliases) in aliases.iter().enumerate() {
+        parsed.extend(aliases.iter().map(|alias| {
+            ParsedPath { index, path: format!("{}{}", prefix, alias), alias: true }
+        }));
+    }
+    parsed
+}
+
+/// `items[index]` for each of the `parsed` paths.
+pub(crate) fn pick<T: Clone>(items: &[T], parsed: &[ParsedPath]) -> Vec<T> {
+    parsed.iter().map(|parsed| items[parsed.index].clone()).collect()
+}
+
+/// The paths of `parsed` to check for duplicates, flattened fields have an empty path.
+pub(crate) fn paths(parsed: &[ParsedPath]) -> Vec<String> {
+    parsed.iter().map(|parsed| parsed.path.clone()).filter(|path| !path.is_empty()).collect()
+}
@@ -0,0 +1,44 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Type};
+use crate::generics::mentions_params;
+
+/// Guard of the `match` arm selecting a field by the path `s`, and the part of `s` passed on to
+/// the field. `#[remote(flatten)]` fields have no path of their own, they are selected by the
+/// paths of their fields (`fields`, e.g. `<<T as RemoteGet>::GetterType as Getter>::FIELDS`),
+/// which are passed on whole.
+pub(crate) fn field_arm(path: &str, fields: &Option<TokenStream>) -> (TokenStream, TokenStream) {
+    match fields {
+        Some(fields) => (quote!(FieldsType::in_fields(#fields, s)), quote!(s)),
+        None => (quote!(FieldsType::starts_with_segment(s, #path)), quote!(&s[#path.len()..])),
+    }
+}
+
+/// Length and value of the array listing the paths of the fields, with the fields of the
+/// `#[remote(flatten)]` fields spliced in at their position.
+pub(crate) fn cases(paths: &[String], fields: &[Option<TokenStream>]) -> (TokenStream, TokenStream) {
+    if fields.iter().all(Option::is_none) {
+        let len = paths.len();
+        return (quote!(#len), quote!([#(#paths,)*]));
+    }
+    let own = fields.iter().filter(|fields| fields.is_none()).count();
+    let flattened = fields.iter().flatten();
+    let parts = paths.iter().zip(fields).map(|(path, fields)| match fields {
+        Some(fields) => fields.clone(),
+        None => quote!(&[#path]),
+    });
+    (quote!(#own #(+ #flattened.len())*), quote!(FieldsType::concat(&[#(#parts),*])))
+}
+
+/// Compile error for a `#[remote(flatten)]` field whose type uses a generic parameter of the
+/// container, as the length of the list of paths has to be known without them, or whose type
+/// has no paths of fields to splice in (`fields`), as it isn't a derived struct.
+pub(crate) fn check_flatten(generics: &Generics, ty: &Type, fields: &TokenStream) -> TokenStream {
+    if mentions_params(generics, ty) {
+        quote!(compile_error!("`#[remote(flatten)]` fields can't depend on generic parameters");)
+    } else {
+        quote! {
+            const _: () = assert!(!#fields.is_empty(), "`#[remote(flatten)]` is only supported on fields of derived structs");
+        }
+    }
+}
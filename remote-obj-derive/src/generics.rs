@@ -0,0 +1,181 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{parse_quote, GenericParam, Generics, Ident, Type, WherePredicate};
+
+/// Whether a type refers to the given generic parameter anywhere in its tokens.
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(i) => &i == ident,
+        TokenTree::Group(g) => mentions(g.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Whether a type uses any of the type or const parameters of `generics`.
+pub(crate) fn mentions_params(generics: &Generics, ty: &Type) -> bool {
+    generics.params.iter()
+        .filter_map(param_ident)
+        .any(|ident| mentions(ty.to_token_stream(), ident))
+}
+
+fn param_ident(param: &GenericParam) -> Option<&Ident> {
+    match param {
+        GenericParam::Type(param) => Some(&param.ident),
+        GenericParam::Const(param) => Some(&param.ident),
+        GenericParam::Lifetime(_) => None,
+    }
+}
+
+/// `#ty: #bound` for every type using one of the parameters, and `T: 'static` for the type
+/// parameters themselves, as the generated enums have to be `'static`.
+fn predicates(params: &[&GenericParam], types: &[Type], bound: &TokenStream) -> Vec<WherePredicate> {
+    let idents: Vec<_> = params.iter().filter_map(|param| param_ident(param)).collect();
+    let mut predicates: Vec<WherePredicate> = params.iter().filter_map(|param| match param {
+        GenericParam::Type(param) => {
+            let ident = &param.ident;
+            Some(parse_quote!(#ident: 'static))
+        }
+        _ => None,
+    }).collect();
+    for ty in types {
+        if idents.iter().any(|ident| mentions(ty.to_token_stream(), ident)) {
+            predicates.push(parse_quote!(#ty: #bound));
+        }
+    }
+    predicates
+}
+
+/// Generics of the container with the bounds the derived impl needs on its field types added.
+pub(crate) fn container_generics(generics: &Generics, types: &[Type], bound: TokenStream) -> Generics {
+    let mut bounded = generics.clone();
+    let params: Vec<_> = generics.params.iter().collect();
+    bounded.make_where_clause().predicates.extend(predicates(&params, types, &bound));
+    bounded
+}
+
+/// Generics of a generated enum, the type and const parameters of the container used by `types`
+/// (or all of them if `all` is set). Bounds of the container are kept unless they refer to a
+/// parameter which is left out. Lifetimes are never carried over as the generated enums are
+/// `'static`.
+pub(crate) fn enum_generics(generics: &Generics, types: &[Type], bound: TokenStream, all: bool) -> Generics {
+    let (params, excluded): (Vec<_>, Vec<_>) = generics.params.iter().partition(|param| match param_ident(param) {
+        Some(ident) => all || types.iter().any(|ty| mentions(ty.to_token_stream(), ident)),
+        None => false,
+    });
+    let excluded: Vec<_> = excluded.iter().map(|param| match param {
+        GenericParam::Lifetime(param) => &param.lifetime.ident,
+        param => param_ident(param).unwrap(),
+    }).collect();
+    let keep = |tokens: TokenStream| !excluded.iter().any(|ident| mentions(tokens.clone(), ident));
+
+    let mut enum_generics = Generics::default();
+    for param in params.iter() {
+        enum_generics.params.push(match param {
+            GenericParam::Type(param) => {
+                let mut param = param.clone();
+                param.bounds = param.bounds.into_iter().filter(|bound| keep(bound.to_token_stream())).collect();
+                param.eq_token = None;
+                param.default = None;
+                GenericParam::Type(param)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                let ty = &param.ty;
+                parse_quote!(const #ident: #ty)
+            }
+            GenericParam::Lifetime(_) => unreachable!(),
+        });
+    }
+    let mut predicates = predicates(&params, types, &bound);
+    if let Some(where_clause) = &generics.where_clause {
+        predicates.extend(where_clause.predicates.iter().filter(|predicate| keep(predicate.to_token_stream())).cloned());
+    }
+    if !predicates.is_empty() {
+        enum_generics.make_where_clause().predicates.extend(predicates);
+    }
+    enum_generics
+}
+
+/// Implements `Clone` and `Copy` for a generated enum. These are written out instead of derived,
+/// as a derive would require the generic parameters themselves to implement the trait rather
+/// than the types stored in the enum.
+pub(crate) fn impl_copy(ident: &Ident, generics: &Generics, field_types: &[TokenStream]) -> TokenStream {
+    let impl_generics = with_bounds(generics, field_types, quote!(Copy));
+    let (impl_generics, ty_generics, where_clause) = impl_generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics Clone for #ident #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics Copy for #ident #ty_generics #where_clause {}
+    }
+}
+
+/// Implements `Default` for a generated enum, which is always the `__None` variant.
+pub(crate) fn impl_default(ident: &Ident, generics: &Generics) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics Default for #ident #ty_generics #where_clause {
+            fn default() -> Self {
+                #ident::__None
+            }
+        }
+    }
+}
+
+/// Implements `PartialEq` (and `Eq` and `Hash` if `hash` is set) for a generated enum, `variants`
+/// are the variants holding a value of the corresponding type in `field_types`.
+pub(crate) fn impl_eq(ident: &Ident, generics: &Generics, variants: &[Ident], field_types: &[TokenStream], hash: bool) -> TokenStream {
+    let eq_generics = with_bounds(generics, field_types, quote!(PartialEq));
+    let (impl_generics, ty_generics, where_clause) = eq_generics.split_for_impl();
+    let mut tokens = quote! {
+        #[automatically_derived]
+        impl #impl_generics PartialEq for #ident #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                match (self, other) {
+                    #((#ident::#variants(a), #ident::#variants(b)) => a == b,)*
+                    _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+                }
+            }
+        }
+    };
+
+    if hash {
+        let eq_generics = with_bounds(generics, field_types, quote!(Eq));
+        let (impl_generics, ty_generics, where_clause) = eq_generics.split_for_impl();
+        let hash_generics = with_bounds(generics, field_types, quote!(core::hash::Hash));
+        let (hash_impl_generics, _, hash_where_clause) = hash_generics.split_for_impl();
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics Eq for #ident #ty_generics #where_clause {}
+
+            #[automatically_derived]
+            impl #hash_impl_generics core::hash::Hash for #ident #ty_generics #hash_where_clause {
+                fn hash<__H: core::hash::Hasher>(&self, state: &mut __H) {
+                    core::hash::Hash::hash(&core::mem::discriminant(self), state);
+                    match self {
+                        #(#ident::#variants(x) => core::hash::Hash::hash(x, state),)*
+                        _ => {}
+                    }
+                }
+            }
+        });
+    }
+    tokens
+}
+
+fn with_bounds(generics: &Generics, field_types: &[TokenStream], bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    if !generics.params.is_empty() {
+        let predicates = &mut generics.make_where_clause().predicates;
+        for ty in field_types {
+            predicates.push(parse_quote!(#ty: #bound));
+        }
+    }
+    generics
+}
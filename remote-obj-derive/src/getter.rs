@@ -1,12 +1,20 @@
 use darling::{ast::{Data, Fields}, FromDeriveInput, FromField, FromVariant};
+use darling::FromMeta;
 use darling::util::PathList;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::{Generics, Ident, Type, Visibility};
-use crate::helper::strip_ref;
+use syn::{Attribute, Generics, Ident, Lit, Meta, MetaNameValue, Path, Type, Visibility};
+use crate::alias::{parsed_paths, paths, pick, ParsedPath};
+use crate::flatten::{cases, check_flatten, field_arm};
+use crate::generics::{container_generics, enum_generics, impl_copy, impl_default, impl_eq};
+use crate::helper::{field_member, field_name, field_variant, strip_ref, type_name, AttrExpr};
+use crate::id::{check_duplicate_ids, with_ids};
+use crate::proxy::{access_type, impl_mirror, proxy_type, MirrorFields, read};
+use crate::range::Range;
+use crate::rename::{check_duplicates, path_name, RenameRule};
 
 #[derive(FromDeriveInput)]
-#[darling(supports(struct_named, enum_any), forward_attrs(derive), attributes(remote))]
+#[darling(supports(struct_any, enum_any), forward_attrs(derive), attributes(remote))]
 pub(crate) struct Receiver {
     ident: Ident,
     generics: Generics,
@@ -14,6 +22,65 @@ pub(crate) struct Receiver {
     vis: Visibility,
     #[darling(default)]
     derive: PathList,
+    #[allow(dead_code)]
+    #[darling(default)]
+    replace: bool,
+    #[darling(default)]
+    rename_all: Option<RenameRule>,
+    #[allow(dead_code)]
+    #[darling(default)]
+    validate: Option<Path>,
+    #[allow(dead_code)]
+    #[darling(default)]
+    on_set: Option<Path>,
+    #[darling(default, multiple)]
+    computed: Vec<Computed>,
+    /// foreign type this is a mirror of, see `impl_mirror`
+    #[darling(default)]
+    remote: Option<Path>,
+}
+
+/// `#[remote(computed(name = "power", ty = "f32", with = "Self::power"))]` on a struct adds a
+/// read-only field `.power`, read by calling `fn(&Self) -> ty`.
+#[derive(FromMeta, Clone)]
+pub(crate) struct Computed {
+    name: Ident,
+    ty: Type,
+    with: Path,
+    #[darling(default)]
+    id: Option<u32>,
+    #[darling(default)]
+    unit: Option<String>,
+    #[darling(default)]
+    description: Option<String>,
+}
+
+impl Computed {
+    fn to_field(&self) -> ReceiverField {
+        ReceiverField {
+            ident: Some(self.name.clone()),
+            ty: self.ty.clone(),
+            index: 0,
+            skip: false,
+            write_only: false,
+            read_only: true,
+            rename: None,
+            range: None,
+            validate: None,
+            on_set: None,
+            id: self.id,
+            attrs: Vec::new(),
+            unit: self.unit.clone(),
+            description: self.description.clone(),
+            default: None,
+            flatten: false,
+            alias: Vec::new(),
+            deprecated: false,
+            opaque: false,
+            with: None,
+            computed: Some(self.with.clone()),
+        }
+    }
 }
 
 
@@ -27,83 +94,333 @@ impl ToTokens for Receiver {
                 self.to_tokens_struct(tokens);
             }
         }
+        if let Some(remote) = &self.remote {
+            tokens.extend(impl_mirror(remote, &self.ident, &self.mirror_fields(), false));
+        }
+    }
+}
+
+impl Receiver {
+    /// All fields of the struct, or of each of the variants of the enum, see `impl_mirror`.
+    fn mirror_fields(&self) -> Vec<(Option<Ident>, MirrorFields)> {
+        let fields = |fields: &Fields<ReceiverField>| -> MirrorFields {
+            fields.iter().enumerate().map(|(index, field)| (field.ident.clone(), index)).collect()
+        };
+        match &self.data {
+            Data::Enum(variants) => variants.iter().map(|v| (Some(v.ident.clone()), fields(&v.fields))).collect(),
+            Data::Struct(struct_fields) => vec![(None, fields(struct_fields))],
+        }
     }
 }
 
 impl Receiver {
     fn getter_fields_to_emit(&self) -> Vec<ReceiverField> {
-        self.data
-            .as_ref()
-            .take_struct()
-            .expect("FieldNames only supports named structs")
-            .into_iter()
-            .filter(|field| {
-                !field.skip && !field.write_only
-            } )
-            .map(|x| (*x).clone())
-            .collect()
+        fields_to_emit(
+            &self.data
+                .as_ref()
+                .take_struct()
+                .expect("FieldNames only supports structs")
+                .map(|x| x.clone())
+        )
+    }
+}
+
+/// Paths of `fields`, e.g. `.a`, see `path_name`. `#[remote(flatten)]` fields have an empty path.
+fn field_paths(fields: &[ReceiverField], rename_all: Option<RenameRule>) -> Vec<String> {
+    fields.iter().map(|field| {
+        if field.flatten {
+            return String::new();
+        }
+        format!(".{}", path_name(&field.ident, field.index, &field.rename, rename_all))
+    }).collect()
+}
+
+/// The paths of the fields of the `#[remote(flatten)]` fields, `None` for other fields.
+fn flattened(fields: &[ReceiverField], types: &[Type]) -> Vec<Option<TokenStream>> {
+    fields.iter().zip(types.iter()).map(|(field, ty)| {
+        field.flatten.then(|| quote!(<<#ty as RemoteGet>::GetterType as Getter>::FIELDS))
+    }).collect()
+}
+
+/// Doc comment from the `#[doc = "..."]` attributes, without the space following `///`.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs.iter().filter_map(|attr| match attr.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(doc), .. })) if path.is_ident("doc") => {
+            let doc = doc.value();
+            Some(doc.strip_prefix(' ').unwrap_or(&doc).to_string())
+        }
+        _ => None,
+    }).collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(lines.join("\n"))
+}
+
+/// Expression adding the attributes of a field or variant to the `metadata` of its type.
+fn metadata(attrs: &[Attribute], unit: &Option<String>, description: &Option<String>, range: Option<TokenStream>, deprecated: bool) -> TokenStream {
+    let mut updates = Vec::new();
+    if let Some(doc) = doc_comment(attrs) {
+        updates.push(quote!(metadata.doc = Some(#doc);));
+    }
+    if let Some(unit) = unit {
+        updates.push(quote!(metadata.unit = Some(#unit);));
+    }
+    if let Some(description) = description {
+        updates.push(quote!(metadata.description = Some(#description);));
+    }
+    if let Some(range) = range {
+        updates.push(quote!(metadata.range = Some(#range);));
+    }
+    if deprecated {
+        updates.push(quote!(metadata.deprecated = true;));
+    }
+    if updates.is_empty() {
+        return quote!(metadata);
+    }
+    quote!({
+        let mut metadata = metadata;
+        #(#updates)*
+        metadata
+    })
+}
+
+/// Metadata of a field, deprecated if it was reached through an `alias`.
+fn field_metadata(field: &ReceiverField, alias: bool) -> TokenStream {
+    let ty = strip_ref(field.ty.clone());
+    let type_name = type_name(&ty);
+    let range = field.range.as_ref().map(|range| range.metadata(&ty));
+    let metadata = metadata(&field.attrs, &field.unit, &field.description, range, field.deprecated || alias);
+    quote!({
+        let metadata = FieldMetadata { type_name: #type_name, ..metadata };
+        #metadata
+    })
+}
+
+/// Metadata of a container or variant without a field type, named `name`.
+fn named_metadata(name: String) -> TokenStream {
+    quote!(FieldMetadata { type_name: #name, ..FieldMetadata::default() })
+}
+
+/// Metadata of the path below a field or variant, marked as deprecated if the field or variant
+/// was reached through an `alias`.
+fn nested_metadata(alias: bool) -> TokenStream {
+    if alias {
+        quote!(FieldMetadata { deprecated: true, ..metadata })
+    } else {
+        quote!(metadata)
     }
 }
 
+/// Paths the fields are parsed from, with the guards and the rests of their `match` arms, see
+/// `field_arm`. Flattened fields have no aliases.
+fn parsed_fields(fields: &[ReceiverField], names_string: &[String], flattened: &[Option<TokenStream>]) -> (Vec<ParsedPath>, Vec<TokenStream>, Vec<TokenStream>) {
+    let aliases: Vec<&[String]> = fields.iter().map(|field| {
+        if field.flatten { &[][..] } else { &field.alias[..] }
+    }).collect();
+    let parsed = parsed_paths(names_string, &aliases, ".");
+    let (guards, rests) = parsed.iter().zip(pick(flattened, &parsed).iter())
+        .map(|(parsed, flattened)| field_arm(&parsed.path, flattened))
+        .unzip();
+    (parsed, guards, rests)
+}
+
+fn fields_to_emit(fields: &Fields<ReceiverField>) -> Vec<ReceiverField> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| {
+            !field.skip && !field.write_only
+        } )
+        .map(|(index, x)| ReceiverField { index, ..x.clone() })
+        .collect()
+}
+
 impl Receiver {
     fn to_tokens_struct(&self, tokens: &mut TokenStream) {
         let ident = &self.ident;
         let getter_enum_ident = format_ident!("{}Getter", ident);
         let value_enum_ident = format_ident!("{}Value", ident);
+        let root_metadata = named_metadata(ident.to_string());
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let fields = self.getter_fields_to_emit();
+        let mut fields = self.getter_fields_to_emit();
+        fields.extend(self.computed.iter().map(Computed::to_field));
 
-        let types: Vec<_> = fields.iter().map(|field|
-            strip_ref(field.ty.clone())
+        // also covers the write-only fields, which share the ids with the setter
+        let struct_fields = self.data.as_ref().take_struct().expect("FieldNames only supports structs");
+        tokens.extend(check_duplicate_ids(
+            struct_fields.iter().map(|field| field.id).chain(self.computed.iter().map(|c| c.id))
+        ));
+
+        let enum_generics = self.to_tokens_fields(tokens, &getter_enum_ident, &value_enum_ident, &fields, self.rename_all);
+        let (_, enum_ty_generics, _) = enum_generics.split_for_impl();
+
+        let types: Vec<_> = fields.iter().map(ReceiverField::access_type).collect();
+
+        let generics = container_generics(&self.generics, &types, quote!(RemoteGet));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let names: Vec<_> = fields.iter().map(|field|
+            field_variant(&field.ident, field.index)
         ).collect();
 
-        let method_names: Vec<_> = fields.clone().into_iter().map(|field| {
-            format_ident!("make_{}", field.ident.unwrap())
+        // computed fields are read into a temporary
+        let values: Vec<_> = fields.iter().map(|field| match &field.computed {
+            Some(with) => quote!(&#with(self)),
+            None => {
+                let member = field_member(&field.ident, field.index);
+                read(&field.ty, &field.proxy(), quote!(&self.#member), false)
+            }
         }).collect();
 
-        let names: Vec<_> = fields.clone().into_iter().map(|field|
-            field.ident.unwrap()
-        ).collect();
+        let names_string = field_paths(&fields, self.rename_all);
+        let (parsed, guards, rests) = parsed_fields(&fields, &names_string, &flattened(&fields, &types));
+        let parsed_types = pick(&types, &parsed);
+        let parsed_values = pick(&values, &parsed);
+
+        tokens.extend(quote! {
+            #[automatically_derived]
+            #[allow(non_snake_case)]
+            impl #impl_generics RemoteGet for #ident #ty_generics #where_clause {
+                type ValueType = #value_enum_ident #enum_ty_generics;
+                type GetterType = #getter_enum_ident #enum_ty_generics;
+
+                fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+                    match x {
+                        #(#getter_enum_ident::#names(x) => Ok(#value_enum_ident::#names(<#types as RemoteGet>::get(#values, x).map_err(Error::nested)?)),)*
+                        #getter_enum_ident::__None => Err(Error::EmptyPath { depth: 0 })
+                    }
+                }
+
+                fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+                    match x {
+                        #(#getter_enum_ident::#names(x) => {
+                            let (x, len) = <#types as RemoteGet>::hydrate(x, buf).map_err(Error::nested)?;
+                            Ok((#value_enum_ident::#names(x), len))
+                        },)*
+                        #getter_enum_ident::__None => Err(Error::EmptyPath { depth: 0 })
+                    }
+                }
+
+                fn dynamic_fields(&self, s: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+                    match &s[..] {
+                        #(s if #guards => {
+                            <#parsed_types as RemoteGet>::dynamic_fields(#parsed_values, #rests, keys)
+                        },)*
+                        s => Self::GetterType::get_fields(s),
+                    }
+                }
+
+                fn get_metadata(s: &str) -> Option<FieldMetadata> {
+                    if s.is_empty() {
+                        return Some(#root_metadata);
+                    }
+                    <#getter_enum_ident #enum_ty_generics>::field_metadata(s)
+                }
+            }
+        })
+    }
+
+    /// Emits the getter and value enums for a list of fields, used for structs and for the
+    /// fields of struct-like and tuple enum variants. Returns the generics of the emitted enums.
+    fn to_tokens_fields(&self, tokens: &mut TokenStream, getter_enum_ident: &Ident, value_enum_ident: &Ident, fields: &[ReceiverField], rename_all: Option<RenameRule>) -> Generics {
+        let types: Vec<_> = fields.iter().map(ReceiverField::access_type).collect();
+
+        let generics = enum_generics(&self.generics, &types, quote!(RemoteGet), false);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-        let names_string: Vec<String> = fields.into_iter().map(|field| {
-            format!(".{}", field.ident.unwrap())
+        let getter_types: Vec<_> = types.iter().map(|ty| quote!(<#ty as RemoteGet>::GetterType)).collect();
+        let value_types: Vec<_> = types.iter().map(|ty| quote!(<#ty as RemoteGet>::ValueType)).collect();
+
+        let method_names: Vec<_> = fields.iter().map(|field| {
+            format_ident!("make_{}", field_name(&field.ident, field.index))
         }).collect();
 
-        let variants_len = names_string.len();
+        let names: Vec<_> = fields.iter().map(|field|
+            field_variant(&field.ident, field.index)
+        ).collect();
+
+        let names_string = field_paths(fields, rename_all);
+        let flattened = flattened(fields, &types);
+        for (ty, flattened) in types.iter().zip(flattened.iter()) {
+            if let Some(flattened) = flattened {
+                tokens.extend(check_flatten(&self.generics, ty, flattened));
+            }
+        }
+        let (cases_len, cases) = cases(&names_string, &flattened);
+
+        // aliases are parsed like the canonical paths, but not listed or displayed
+        let (parsed, guards, rests) = parsed_fields(fields, &names_string, &flattened);
+        tokens.extend(check_duplicates(&paths(&parsed)));
+        let parsed_names = pick(&names, &parsed);
+        let parsed_types = pick(&types, &parsed);
+        let parsed_metadata: Vec<_> = parsed.iter().map(|parsed| field_metadata(&fields[parsed.index], parsed.alias)).collect();
+        let parsed_nested_metadata: Vec<_> = parsed.iter().map(|parsed| nested_metadata(parsed.alias)).collect();
+
+        let (id_names, ids) = with_ids(names.iter().zip(fields.iter().map(|field| field.id)));
+
+        // computed fields can't be written, so they can't be reset either
+        let reset_setters: Vec<_> = fields.iter().map(|field| match &field.computed {
+            Some(_) => quote!(None),
+            None => {
+                let index = field.index;
+                quote!(__S::reset_in(ResetSegment::Field(#index), x))
+            }
+        }).collect();
 
         let vis = &self.vis;
         let inner_derives = &self.derive;
 
+        tokens.extend(impl_copy(getter_enum_ident, &generics, &getter_types));
+        tokens.extend(impl_default(getter_enum_ident, &generics));
+        tokens.extend(impl_eq(getter_enum_ident, &generics, &names, &getter_types, true));
+        tokens.extend(impl_copy(value_enum_ident, &generics, &value_types));
+        tokens.extend(impl_eq(value_enum_ident, &generics, &names, &value_types, false));
+
         tokens.extend(quote! {
             #[automatically_derived]
-            #[derive(Default, Clone, Hash, PartialEq, Eq, Copy)]
             #[derive(#(#inner_derives),*)]
             #[allow(non_camel_case_types)]
-            #vis enum #getter_enum_ident {
+            #vis enum #getter_enum_ident #impl_generics #where_clause {
                 #(#names(<#types as RemoteGet>::GetterType),)*
-                #[default]
                 __None,
             }
 
             #[automatically_derived]
             #[allow(non_snake_case)]
-            impl #impl_generics #getter_enum_ident {
+            impl #impl_generics #getter_enum_ident #ty_generics #where_clause {
                 #(#vis fn #method_names<F>(&self, func: F) -> Self where F: Fn(<#types as RemoteGet>::GetterType) -> <#types as RemoteGet>::GetterType {
                     #getter_enum_ident::#names(func(<#types as RemoteGet>::GetterType::default()))
                 })*
 
-                const GETTER_CASES: [&'static str; #variants_len] = [
-                    #(#names_string,)*
-                ];
+                const GETTER_CASES: [&'static str; #cases_len] = #cases;
+
+                /// `RemoteGet::get_metadata` for the path of a field
+                fn field_metadata(s: &str) -> Option<FieldMetadata> {
+                    match &s[..] {
+                        #(s if #guards => {
+                            let s = #rests;
+                            let metadata = <#parsed_types as RemoteGet>::get_metadata(s)?;
+                            if !s.is_empty() {
+                                return Some(#parsed_nested_metadata);
+                            }
+                            return Some(#parsed_metadata);
+                        },)*
+                        _ => {
+                            return None;
+                        }
+                    };
+                }
             }
 
-            impl Getter for #getter_enum_ident {
+            impl #impl_generics Getter for #getter_enum_ident #ty_generics #where_clause {
+                const FIELDS: &'static [&'static str] = &Self::GETTER_CASES;
+
                 fn parse_getter(s: &str) -> Option<Self> {
                     match &s[..] {
-                        #(s if s.starts_with(#names_string) => {
-                            return Some(#getter_enum_ident::#names(<#types as RemoteGet>::GetterType::parse_getter(&s[#names_string.len()..])?));
-                        })*,
+                        #(s if #guards => {
+                            return Some(#getter_enum_ident::#parsed_names(<#parsed_types as RemoteGet>::GetterType::parse_getter(#rests)?));
+                        },)*
                         _ => {
                             return None;
                         }
@@ -113,26 +430,50 @@ impl Receiver {
                 fn get_fields(s: &str) -> Option<FieldsType> {
                     match &s[..] {
                         "" => return Some(FieldsType::Fields(&Self::GETTER_CASES)),
-                        #(s if s.starts_with(#names_string) => {
-                            return <#types as RemoteGet>::GetterType::get_fields(&s[#names_string.len()..]);
-                        })*,
+                        #(s if #guards => {
+                            return <#parsed_types as RemoteGet>::GetterType::get_fields(#rests);
+                        },)*
                         _ => {
                             return None;
                         }
                     };
                 }
+
+                fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+                    match self {
+                        #(#getter_enum_ident::#id_names(x) => {
+                            let (id, ids) = ids.split_first_mut()?;
+                            *id = #ids;
+                            Some(x.to_ids(ids)? + 1)
+                        },)*
+                        _ => None,
+                    }
+                }
+
+                fn parse_getter_ids(ids: &[u32]) -> Option<Self> {
+                    match ids {
+                        #([#ids, ids @ ..] => Some(#getter_enum_ident::#id_names(Getter::parse_getter_ids(ids)?)),)*
+                        _ => None,
+                    }
+                }
+
+                #[allow(unused_variables)]
+                fn reset_setter<__S: Setter>(&self) -> Option<__S> {
+                    match self {
+                        #(#getter_enum_ident::#names(x) => #reset_setters,)*
+                        #getter_enum_ident::__None => __S::reset_whole(),
+                    }
+                }
             }
 
-            impl core::fmt::Display for #getter_enum_ident {
+            impl #impl_generics core::fmt::Display for #getter_enum_ident #ty_generics #where_clause {
                 fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     match self {
                         #(#getter_enum_ident::#names(ref x) => {
-                            write!(f, #names_string)?;
+                            write!(f, "{}", #names_string)?;
                             write!(f, "{}", x)
                         },)*
-                        _ => {
-                            unreachable!();
-                        }
+                        #getter_enum_ident::__None => Ok(())
                     }
                 }
             }
@@ -140,37 +481,12 @@ impl Receiver {
             #[automatically_derived]
             #[allow(non_camel_case_types)]
             #[derive(#(#inner_derives),*)]
-            #[derive(Copy, Clone, PartialEq)]
-            #vis enum #value_enum_ident {
+            #vis enum #value_enum_ident #impl_generics #where_clause {
                 #(#names(<#types as RemoteGet>::ValueType)),*
             }
 
-            #[automatically_derived]
             #[allow(non_snake_case)]
-            impl #impl_generics RemoteGet for #ident #ty_generics #where_clause {
-                type ValueType = #value_enum_ident;
-                type GetterType = #getter_enum_ident;
-
-                fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, ()> {
-                    Ok(match x {
-                        #(#getter_enum_ident::#names(x) => #value_enum_ident::#names(self.#names.get(x)?),)*
-                        #getter_enum_ident::__None => { unimplemented!() }
-                    })
-                }
-
-                fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), ()> {
-                    match x {
-                        #(#getter_enum_ident::#names(x) => {
-                            let (x, len) = <#types as RemoteGet>::hydrate(x, buf)?;
-                            Ok((#value_enum_ident::#names(x), len))
-                        },)*
-                        #getter_enum_ident::__None => { unimplemented!() }
-                    }
-                }
-            }
-
-            #[allow(non_snake_case)]
-            impl #impl_generics #value_enum_ident {
+            impl #impl_generics #value_enum_ident #ty_generics #where_clause {
                 #(fn #names(self) -> <#types as RemoteGet>::ValueType {
                     match self {
                         #value_enum_ident::#names(x) => x,
@@ -179,11 +495,16 @@ impl Receiver {
                 })*
             }
 
-            impl #impl_generics Value for #value_enum_ident {
+            impl #impl_generics Value for #value_enum_ident #ty_generics #where_clause {
                 fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
-                    match self {
+                    match *self {
                         #(#value_enum_ident::#names(inner) => inner.dehydrate(x), )*
-                        _ => unreachable!(),
+                    }
+                }
+
+                fn dehydrated_len(&self) -> usize {
+                    match *self {
+                        #(#value_enum_ident::#names(inner) => inner.dehydrated_len(), )*
                     }
                 }
 
@@ -194,11 +515,11 @@ impl Receiver {
                     }
                 }
 
-                fn parse_value<T: Sized>(self, x: &str) -> Option<T> {
+                fn parse_value<__T: 'static>(self, x: &str) -> Option<__T> {
                     match &x[..] {
-                        #(s if s.starts_with(#names_string) => {
+                        #(s if #guards => {
                             return match self {
-                                #value_enum_ident::#names(x) => x.parse_value(&s[#names_string.len()..]),
+                                #value_enum_ident::#parsed_names(x) => x.parse_value(#rests),
                                 _ => None
                             }
                         },)*
@@ -208,32 +529,107 @@ impl Receiver {
                     };
                 }
             }
-        })
+        });
+
+        generics
     }
 }
 
 #[derive(FromField, Clone)]
-#[darling(attributes(remote))]
+#[darling(attributes(remote), forward_attrs(doc))]
 #[allow(dead_code)]
 struct ReceiverField {
     ident: Option<Ident>,
     ty: Type,
+    attrs: Vec<Attribute>,
+    #[darling(skip)]
+    index: usize,
     #[darling(default)]
     skip: bool,
     #[darling(default)]
     write_only: bool,
     #[darling(default)]
     read_only: bool,
+    #[darling(default)]
+    rename: Option<String>,
+    #[darling(default)]
+    range: Option<Range>,
+    #[darling(default)]
+    validate: Option<Path>,
+    #[darling(default)]
+    on_set: Option<Path>,
+    #[darling(default)]
+    id: Option<u32>,
+    #[darling(default)]
+    unit: Option<String>,
+    #[darling(default)]
+    description: Option<String>,
+    #[darling(default)]
+    default: Option<AttrExpr>,
+    /// splices the fields of the field's type into the paths of the container
+    #[darling(default)]
+    flatten: bool,
+    /// other paths the field is parsed from, e.g. its path before it was renamed
+    #[darling(default, multiple)]
+    alias: Vec<String>,
+    #[darling(default)]
+    deprecated: bool,
+    /// reads the field as a whole through `Opaque<T>`
+    #[darling(default)]
+    opaque: bool,
+    /// mirror of the foreign type of the field, reading it through `Proxy`
+    #[darling(default)]
+    with: Option<Type>,
+    /// getter function of a computed field, see `Computed`
+    #[darling(skip)]
+    computed: Option<Path>,
+}
+
+impl ReceiverField {
+    fn proxy(&self) -> Option<Type> {
+        proxy_type(&self.ty, self.opaque, &self.with)
+    }
+
+    fn access_type(&self) -> Type {
+        access_type(&self.ty, &self.proxy())
+    }
 }
 
 impl Receiver {
+    fn variant(&self, ident: &Ident) -> &ReceiverVariant {
+        self.data
+            .as_ref()
+            .take_enum()
+            .expect("VariantNames only takes enums")
+            .into_iter()
+            .find(|v| &v.ident == ident)
+            .unwrap()
+    }
+
+    /// Index of a variant in the declaration of the enum, see `ResetSegment::Field`.
+    fn variant_index(&self, ident: &Ident) -> usize {
+        self.data
+            .as_ref()
+            .take_enum()
+            .expect("VariantNames only takes enums")
+            .iter()
+            .position(|v| &v.ident == ident)
+            .unwrap()
+    }
+
+    /// Path of a variant, e.g. `::A`, see `path_name`.
+    fn variant_path(&self, ident: &Ident) -> String {
+        let variant = self.variant(ident);
+        format!("::{}", path_name(&Some(variant.ident.clone()), 0, &variant.rename, self.rename_all))
+    }
+
     fn unit_variants(&self) -> Vec<Ident> {
         self.data
             .as_ref()
             .take_enum()
             .expect("VariantNames only takes enums")
             .into_iter()
-            .filter(|v| v.fields.is_unit() && !v.skip && !v.read_only)
+            .filter(|v| v.fields.is_unit() && !v.skip && !v.write_only)
             .map(|v| v.ident.clone())
             .collect()
     }
@@ -244,7 +640,7 @@ impl Receiver {
             .take_enum()
             .expect("VariantNames only takes enums")
             .into_iter()
-            .filter(|v| v.fields.is_newtype() && !v.skip && !v.read_only)
+            .filter(|v| v.fields.is_newtype() && !v.skip && !v.write_only)
             .map(|v| v.ident.clone())
             .collect()
     }
@@ -255,91 +651,279 @@ impl Receiver {
             .take_enum()
             .expect("VariantNames only takes enums")
             .into_iter()
-            .filter(|v| v.fields.is_newtype())
-            .map(|v| strip_ref(v.fields.fields.first().unwrap().ty.clone()))
+            .filter(|v| v.fields.is_newtype() && !v.skip && !v.write_only)
+            .map(|v| v.fields.fields[0].access_type())
             .collect()
     }
 
-    fn other_varient_names(&self) -> Vec<Ident> {
+    /// struct-like variants and tuple variants with more than one field
+    fn struct_variants(&self) -> Vec<ReceiverVariant> {
         self.data
             .as_ref()
             .take_enum()
             .expect("VariantNames only takes enums")
             .into_iter()
-            .filter(|v| !v.fields.is_unit() && !v.fields.is_newtype())
-            .map(|v| v.ident.clone())
+            .filter(|v| !v.fields.is_unit() && !v.fields.is_newtype() && !v.skip && !v.write_only)
+            .cloned()
             .collect()
     }
 }
 
 impl Receiver {
     fn to_tokens_enum(&self, tokens: &mut TokenStream) {
-        let other_varient_names = self.other_varient_names();
-        if other_varient_names.len() > 0 {
-            panic!("VariantNames only supports enums with no unit or newtype variants, {:?}", other_varient_names);
-        }
-
         let ident = &self.ident;
+
+        if !self.computed.is_empty() {
+            tokens.extend(quote!(compile_error!("`#[remote(computed(..))]` is only supported on structs");));
+        }
         let getter_enum_ident = format_ident!("{}Getter", ident);
         let value_enum_ident = format_ident!("{}Value", ident);
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-
         let unit_variants = self.unit_variants();
         let newtype_variants = self.newtype_variants();
         let newtype_types = self.newtype_types();
+        let struct_variants = self.struct_variants();
 
-        let newtype_method_names: Vec<_> = newtype_variants.iter().map(|field| {
+        // all types reachable through the enum, which decide the generics of the generated enums
+        let mut types = newtype_types.clone();
+        for variant in struct_variants.iter() {
+            types.extend(fields_to_emit(&variant.fields).iter().map(ReceiverField::access_type));
+        }
+        let generics = container_generics(&self.generics, &types, quote!(RemoteGet));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let enum_generics = enum_generics(&self.generics, &types, quote!(RemoteGet), false);
+        let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+
+        // newtype and struct variants are both addressed with `::Variant` followed by the path
+        // into the variant, they only differ in how the inner getter is applied
+        let mut data_variants = Vec::new();
+        let mut data_getter_types = Vec::new();
+        let mut data_value_types = Vec::new();
+        let mut get_arms = Vec::new();
+        let mut hydrate_arms = Vec::new();
+        let mut dynamic_fields_arms = Vec::new();
+        let mut metadata_lookups = Vec::new();
+        let mut variants_metadata = Vec::new();
+        // metadata of the variants reached through an alias
+        let mut aliases_metadata = Vec::new();
+
+        for (variant, ty) in newtype_variants.iter().zip(newtype_types.iter()) {
+            let field = &self.variant(variant).fields.fields[0];
+            let inner = read(&field.ty, &field.proxy(), quote!(x), false);
+            let value_variant = format_ident!("{}Value", variant);
+            data_variants.push(variant.clone());
+            data_getter_types.push(quote!(<#ty as RemoteGet>::GetterType));
+            data_value_types.push(quote!(<#ty as RemoteGet>::ValueType));
+            get_arms.push(quote! {
+                #getter_enum_ident::#variant(inner) => match self {
+                    Self::#variant(x) => Ok(#value_enum_ident::#value_variant(<#ty as RemoteGet>::get(#inner, inner).map_err(Error::nested)?)),
+                    _ => Err(Error::WrongVariant { depth: 0 })
+                }
+            });
+            hydrate_arms.push(quote! {
+                #getter_enum_ident::#variant(x) => {
+                    let (x, len) = <#ty as RemoteGet>::hydrate(x, buf).map_err(Error::nested)?;
+                    Ok((#value_enum_ident::#value_variant(x), len))
+                }
+            });
+            dynamic_fields_arms.push(quote! {
+                Self::#variant(x) => <#ty as RemoteGet>::dynamic_fields(#inner, s, keys)
+            });
+            metadata_lookups.push(quote!(<#ty as RemoteGet>::get_metadata(s)?));
+            // attributes of the variant take precedence over the ones of its field
+            let v = self.variant(variant);
+            let newtype_metadata = |alias: bool| {
+                let field_metadata = field_metadata(&v.fields.fields[0], false);
+                let variant_metadata = metadata(&v.attrs, &v.unit, &v.description, None, v.deprecated || alias);
+                quote!({
+                    let metadata = #field_metadata;
+                    #variant_metadata
+                })
+            };
+            variants_metadata.push(newtype_metadata(false));
+            aliases_metadata.push(newtype_metadata(true));
+        }
+
+        for variant in struct_variants.iter() {
+            let variant_ident = &variant.ident;
+            let value_variant = format_ident!("{}Value", variant_ident);
+            let inner_getter_ident = format_ident!("{}{}Getter", ident, variant_ident);
+            let inner_value_ident = format_ident!("{}{}Value", ident, variant_ident);
+
+            let fields = fields_to_emit(&variant.fields);
+            let inner_generics = self.to_tokens_fields(tokens, &inner_getter_ident, &inner_value_ident, &fields, None);
+            let (_, inner_ty_generics, _) = inner_generics.split_for_impl();
+
+            let types: Vec<_> = fields.iter().map(ReceiverField::access_type).collect();
+            let names: Vec<_> = fields.iter().map(|field|
+                field_variant(&field.ident, field.index)
+            ).collect();
+            let members: Vec<_> = fields.iter().map(|field|
+                field_member(&field.ident, field.index)
+            ).collect();
+            let bindings: Vec<_> = fields.iter().map(|field|
+                format_ident!("__field_{}", field_name(&field.ident, field.index))
+            ).collect();
+            let names_string = field_paths(&fields, None);
+            let (parsed, guards, rests) = parsed_fields(&fields, &names_string, &flattened(&fields, &types));
+            let values: Vec<_> = fields.iter().zip(bindings.iter()).map(|(field, binding)| {
+                read(&field.ty, &field.proxy(), quote!(#binding), false)
+            }).collect();
+            let parsed_types = pick(&types, &parsed);
+            let parsed_values = pick(&values, &parsed);
+
+            data_variants.push(variant_ident.clone());
+            data_getter_types.push(quote!(#inner_getter_ident #inner_ty_generics));
+            data_value_types.push(quote!(#inner_value_ident #inner_ty_generics));
+            get_arms.push(quote! {
+                #getter_enum_ident::#variant_ident(inner) => match self {
+                    Self::#variant_ident { #(#members: #bindings,)* .. } => {
+                        let inner = match inner {
+                            #(#inner_getter_ident::#names(x) => <#types as RemoteGet>::get(#values, x).map(#inner_value_ident::#names).map_err(Error::nested),)*
+                            #inner_getter_ident::__None => Err(Error::EmptyPath { depth: 0 })
+                        };
+                        Ok(#value_enum_ident::#value_variant(inner.map_err(Error::nested)?))
+                    },
+                    _ => Err(Error::WrongVariant { depth: 0 })
+                }
+            });
+            hydrate_arms.push(quote! {
+                #getter_enum_ident::#variant_ident(x) => {
+                    let inner = match x {
+                        #(#inner_getter_ident::#names(x) => {
+                            <#types as RemoteGet>::hydrate(x, buf)
+                                .map(|(x, len)| (#inner_value_ident::#names(x), len))
+                                .map_err(Error::nested)
+                        },)*
+                        #inner_getter_ident::__None => Err(Error::EmptyPath { depth: 0 })
+                    };
+                    let (x, len) = inner.map_err(Error::nested)?;
+                    Ok((#value_enum_ident::#value_variant(x), len))
+                }
+            });
+            dynamic_fields_arms.push(quote! {
+                Self::#variant_ident { #(#members: #bindings,)* .. } => match s {
+                    #(s if #guards => {
+                        <#parsed_types as RemoteGet>::dynamic_fields(#parsed_values, #rests, keys)
+                    },)*
+                    s => <#inner_getter_ident #inner_ty_generics as Getter>::get_fields(s),
+                }
+            });
+            let variant_metadata = named_metadata(format!("{}::{}", ident, variant_ident));
+            metadata_lookups.push(quote! {
+                if s.is_empty() {
+                    #variant_metadata
+                } else {
+                    <#inner_getter_ident #inner_ty_generics>::field_metadata(s)?
+                }
+            });
+            variants_metadata.push(metadata(&variant.attrs, &variant.unit, &variant.description, None, variant.deprecated));
+            aliases_metadata.push(metadata(&variant.attrs, &variant.unit, &variant.description, None, true));
+        }
+
+        let data_method_names: Vec<_> = data_variants.iter().map(|field| {
             format_ident!("make_{}", field)
         }).collect();
-        let newtype_value_variants: Vec<_> = newtype_variants.iter().map(|field| {
+        let data_value_variants: Vec<_> = data_variants.iter().map(|field| {
             format_ident!("{}Value", field)
         }).collect();
+        let data_variant_indices: Vec<_> = data_variants.iter().map(|variant| self.variant_index(variant)).collect();
         let vis = &self.vis;
         let inner_derives = &self.derive;
 
-        let newtype_names_string: Vec<String> = newtype_variants.clone().into_iter().map(|field| {
-            format!("::{}", field)
+        let data_names_string: Vec<String> = data_variants.iter().map(|field| {
+            self.variant_path(field)
         }).collect();
+        let unit_names_string: Vec<String> = unit_variants.iter().map(|field| {
+            self.variant_path(field)
+        }).collect();
+
+        // aliases are parsed like the canonical paths, but not listed or displayed
+        let data_aliases: Vec<&[String]> = data_variants.iter().map(|variant| &self.variant(variant).alias[..]).collect();
+        let parsed_data = parsed_paths(&data_names_string, &data_aliases, "::");
+        let unit_aliases: Vec<&[String]> = unit_variants.iter().map(|variant| &self.variant(variant).alias[..]).collect();
+        let parsed_unit = parsed_paths(&unit_names_string, &unit_aliases, "::");
+        tokens.extend(check_duplicates(&[paths(&parsed_data), paths(&parsed_unit)].concat()));
 
-        let variants_len = newtype_variants.len() + 1;
+        let parsed_data_names_string = paths(&parsed_data);
+        let parsed_data_variants = pick(&data_variants, &parsed_data);
+        let parsed_data_value_variants = pick(&data_value_variants, &parsed_data);
+        let parsed_data_getter_types = pick(&data_getter_types, &parsed_data);
+        let parsed_metadata_lookups = pick(&metadata_lookups, &parsed_data);
+        let parsed_variants_metadata: Vec<_> = parsed_data.iter().map(|parsed| {
+            if parsed.alias { &aliases_metadata[parsed.index] } else { &variants_metadata[parsed.index] }
+        }).collect();
+        let parsed_nested_metadata: Vec<_> = parsed_data.iter().map(|parsed| nested_metadata(parsed.alias)).collect();
+        // all paths of each data variant, in the order of `data_variants`
+        let data_variant_paths: Vec<Vec<String>> = (0..data_variants.len()).map(|index| {
+            parsed_data.iter().filter(|parsed| parsed.index == index).map(|parsed| parsed.path.clone()).collect()
+        }).collect();
+
+        let all_variants = self.data.as_ref().take_enum().expect("VariantNames only takes enums");
+        tokens.extend(check_duplicate_ids(all_variants.iter().map(|v| v.id)));
+        for variant in all_variants.iter() {
+            tokens.extend(check_duplicate_ids(variant.fields.iter().map(|field| field.id)));
+        }
+        let (id_variants, ids) = with_ids(data_variants.iter().map(|variant| (variant, self.variant(variant).id)));
+
+        let parsed_unit_names_string = paths(&parsed_unit);
+        let unit_variants_metadata: Vec<_> = parsed_unit.iter().map(|parsed| {
+            let v = self.variant(&unit_variants[parsed.index]);
+            metadata(&v.attrs, &v.unit, &v.description, None, v.deprecated || parsed.alias)
+        }).collect();
+        let unit_variants_names: Vec<_> = parsed_unit.iter().map(|parsed| {
+            named_metadata(format!("{}::{}", ident, unit_variants[parsed.index]))
+        }).collect();
+        let root_metadata = named_metadata(ident.to_string());
+
+        let variants_len = data_variants.len() + 1;
+
+        let struct_variant_names: Vec<_> = struct_variants.iter().map(|v| v.ident.clone()).collect();
+
+        // the result of `GetVariant` is dehydrated as a single byte tag
+        let tagged_variants: Vec<_> = unit_variants.iter().chain(data_variants.iter()).cloned().collect();
+        let variant_tags: Vec<_> = (0..tagged_variants.len()).map(|i| i as u8).collect();
+
+        tokens.extend(impl_copy(&getter_enum_ident, &enum_generics, &data_getter_types));
+        tokens.extend(impl_default(&getter_enum_ident, &enum_generics));
+        tokens.extend(impl_eq(&getter_enum_ident, &enum_generics, &data_variants, &data_getter_types, true));
+        tokens.extend(impl_copy(&value_enum_ident, &enum_generics, &data_value_types));
+        tokens.extend(impl_eq(&value_enum_ident, &enum_generics, &data_value_variants, &data_value_types, false));
 
         tokens.extend(quote! {
             #[automatically_derived]
-            #[derive(Default, Clone, Hash, PartialEq, Eq, Copy)]
             #[derive(#(#inner_derives),*)]
             #[allow(non_camel_case_types)]
-            #vis enum #getter_enum_ident {
+            #vis enum #getter_enum_ident #enum_impl_generics #enum_where_clause {
                 GetVariant,
-                #(#newtype_variants(<#newtype_types as RemoteGet>::GetterType),)*
-                #[default]
+                #(#data_variants(#data_getter_types),)*
                 __None,
             }
 
             #[automatically_derived]
             #[allow(non_snake_case)]
-            impl #impl_generics #getter_enum_ident #ty_generics {
+            impl #enum_impl_generics #getter_enum_ident #enum_ty_generics #enum_where_clause {
                 #vis fn make_var<F>(&self, func: F) -> Self where F: Fn(()) -> NullGetter {
                     #getter_enum_ident::GetVariant
                 }
 
-                #(#vis fn #newtype_method_names<F>(&self, func: F) -> Self where F: Fn(<#newtype_types as RemoteGet>::GetterType) -> <#newtype_types as RemoteGet>::GetterType {
-                    #getter_enum_ident::#newtype_variants(func(<#newtype_types as RemoteGet>::GetterType::default()))
+                #(#vis fn #data_method_names<F>(&self, func: F) -> Self where F: Fn(#data_getter_types) -> #data_getter_types {
+                    #getter_enum_ident::#data_variants(func(<#data_getter_types>::default()))
                 })*
 
                 const GETTER_CASES: [&'static str; #variants_len] = [
-                    #(#newtype_names_string,)*
+                    #(#data_names_string,)*
                     "VARIANT"
                 ];
             }
 
-            impl Getter for #getter_enum_ident {
+            impl #enum_impl_generics Getter for #getter_enum_ident #enum_ty_generics #enum_where_clause {
                 fn parse_getter(s: &str) -> Option<Self> {
                     match &s[..] {
                         "VARIANT" => return Some(#getter_enum_ident::GetVariant),
-                        #(s if s.starts_with(#newtype_names_string) => {
-                            return Some(#getter_enum_ident::#newtype_variants(<#newtype_types as RemoteGet>::GetterType::parse_getter(&s[#newtype_names_string.len()..])?));
-                        })*,
+                        #(s if FieldsType::starts_with_segment(s, #parsed_data_names_string) => {
+                            return Some(#getter_enum_ident::#parsed_data_variants(<#parsed_data_getter_types>::parse_getter(&s[#parsed_data_names_string.len()..])?));
+                        },)*
                         _ => {
                             return None;
                         }
@@ -349,29 +933,56 @@ impl Receiver {
                 fn get_fields(s: &str) -> Option<FieldsType> {
                     match &s[..] {
                         "" => return Some(FieldsType::Fields(&Self::GETTER_CASES)),
-                        #(s if s.starts_with(#newtype_names_string) => {
-                            return <#newtype_types as RemoteGet>::GetterType::get_fields(&s[#newtype_names_string.len()..]);
-                        })*,
+                        #(s if FieldsType::starts_with_segment(s, #parsed_data_names_string) => {
+                            return <#parsed_data_getter_types>::get_fields(&s[#parsed_data_names_string.len()..]);
+                        },)*
                         _ => {
                             return None;
                         }
                     };
                 }
+
+                // the empty numeric path reads the variant
+                fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+                    match self {
+                        #getter_enum_ident::GetVariant => Some(0),
+                        #(#getter_enum_ident::#id_variants(x) => {
+                            let (id, ids) = ids.split_first_mut()?;
+                            *id = #ids;
+                            Some(x.to_ids(ids)? + 1)
+                        },)*
+                        _ => None,
+                    }
+                }
+
+                fn parse_getter_ids(ids: &[u32]) -> Option<Self> {
+                    match ids {
+                        [] => Some(#getter_enum_ident::GetVariant),
+                        #([#ids, ids @ ..] => Some(#getter_enum_ident::#id_variants(Getter::parse_getter_ids(ids)?)),)*
+                        _ => None,
+                    }
+                }
+
+                fn reset_setter<__S: Setter>(&self) -> Option<__S> {
+                    match self {
+                        #getter_enum_ident::GetVariant => None,
+                        #(#getter_enum_ident::#data_variants(x) => __S::reset_in(ResetSegment::Field(#data_variant_indices), x),)*
+                        #getter_enum_ident::__None => __S::reset_whole(),
+                    }
+                }
             }
 
-            impl core::fmt::Display for #getter_enum_ident {
+            impl #enum_impl_generics core::fmt::Display for #getter_enum_ident #enum_ty_generics #enum_where_clause {
                 fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     match self {
-                        #(#getter_enum_ident::#newtype_variants(ref x) => {
-                            write!(f, #newtype_names_string)?;
+                        #(#getter_enum_ident::#data_variants(ref x) => {
+                            write!(f, "{}", #data_names_string)?;
                             write!(f, "{}", x)?;
                         },)*
                         #getter_enum_ident::GetVariant => {
                             write!(f, "GetVariant")?;
                         },
-                        _ => {
-                            unreachable!();
-                        }
+                        #getter_enum_ident::__None => {}
                     }
                     Ok(())
                 }
@@ -380,78 +991,126 @@ impl Receiver {
             #[automatically_derived]
             #[derive(#(#inner_derives),*)]
             #[allow(non_camel_case_types)]
-            #[derive(Copy, Clone, PartialEq)]
-            #vis enum #value_enum_ident {
-                #(#newtype_value_variants(<#newtype_types as RemoteGet>::ValueType),)*
+            #vis enum #value_enum_ident #enum_impl_generics #enum_where_clause {
+                #(#data_value_variants(#data_value_types),)*
                 #(#unit_variants,)*
-                #(#newtype_variants,)*
+                #(#data_variants,)*
             }
 
             #[automatically_derived]
             #[allow(non_snake_case)]
             impl #impl_generics RemoteGet for #ident #ty_generics #where_clause {
-                type ValueType = #value_enum_ident #ty_generics;
-                type GetterType = #getter_enum_ident #ty_generics;
+                type ValueType = #value_enum_ident #enum_ty_generics;
+                type GetterType = #getter_enum_ident #enum_ty_generics;
 
-                fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, ()> {
-                    Ok(match x {
+                fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+                    match x {
                         #getter_enum_ident::GetVariant => {
-                            match self {
+                            Ok(match self {
                                 #(#ident::#newtype_variants(_) => #value_enum_ident::#newtype_variants,)*
+                                #(#ident::#struct_variant_names { .. } => #value_enum_ident::#struct_variant_names,)*
                                 #(#ident::#unit_variants => #value_enum_ident::#unit_variants,)*
-                            }
-                        }
-                        #(#getter_enum_ident::#newtype_variants(inner) => {
-                            #value_enum_ident::#newtype_value_variants(match self {
-                                Self::#newtype_variants(x) => x.get(inner)?,
-                                _ => return Err(())
+                                #[allow(unreachable_patterns)]
+                                _ => return Err(Error::WrongVariant { depth: 0 }),
                             })
-                        },)*
-                        #getter_enum_ident::__None => { unimplemented!() }
-                    })
+                        }
+                        #(#get_arms,)*
+                        #getter_enum_ident::__None => Err(Error::EmptyPath { depth: 0 })
+                    }
                 }
 
-                fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), ()> {
+                fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
                     match x {
-                        #(#getter_enum_ident::#newtype_variants(x) => {
-                            let (x, len) = <#newtype_types as RemoteGet>::hydrate(x, buf)?;
-                            Ok((#value_enum_ident::#newtype_value_variants(x), len))
-                        },)*
-                        _ => { unimplemented!() }
+                        #(#hydrate_arms,)*
+                        #getter_enum_ident::GetVariant => {
+                            match *buf.first().ok_or(Error::BufferTooShort { depth: 0 })? {
+                                #(#variant_tags => Ok((#value_enum_ident::#tagged_variants, 1)),)*
+                                _ => Err(Error::InvalidEncoding { depth: 0 }),
+                            }
+                        },
+                        #getter_enum_ident::__None => Err(Error::EmptyPath { depth: 0 })
+                    }
+                }
+
+                #[allow(unreachable_code, unreachable_patterns)]
+                fn dynamic_fields(&self, s: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+                    // only the active variant can be resolved, others fall back to `get_fields`
+                    let names: &[&str] = match self {
+                        #(#ident::#data_variants { .. } => &[#(#data_variant_paths),*],)*
+                        _ => return Self::GetterType::get_fields(s),
+                    };
+                    let s = match names.iter().find(|name| FieldsType::starts_with_segment(s, name)) {
+                        Some(name) => &s[name.len()..],
+                        None => return Self::GetterType::get_fields(s),
+                    };
+                    match self {
+                        #(#dynamic_fields_arms,)*
+                        _ => unreachable!(),
                     }
                 }
+
+                fn get_metadata(s: &str) -> Option<FieldMetadata> {
+                    match &s[..] {
+                        "" => return Some(#root_metadata),
+                        #(#parsed_unit_names_string => {
+                            let metadata = #unit_variants_names;
+                            return Some(#unit_variants_metadata);
+                        },)*
+                        #(s if FieldsType::starts_with_segment(s, #parsed_data_names_string) => {
+                            let s = &s[#parsed_data_names_string.len()..];
+                            let metadata = #parsed_metadata_lookups;
+                            if !s.is_empty() {
+                                return Some(#parsed_nested_metadata);
+                            }
+                            return Some(#parsed_variants_metadata);
+                        },)*
+                        _ => {
+                            return None;
+                        }
+                    };
+                }
             }
 
             #[allow(non_snake_case)]
-            impl #impl_generics #value_enum_ident #ty_generics {
-                #(fn #newtype_variants(self) -> <#newtype_types as RemoteGet>::ValueType {
+            impl #enum_impl_generics #value_enum_ident #enum_ty_generics #enum_where_clause {
+                #(fn #data_variants(self) -> #data_value_types {
                     match self {
-                        Self::#newtype_value_variants(x) => x,
+                        Self::#data_value_variants(x) => x,
                         _ => unreachable!(),
                     }
                 })*
             }
 
-            impl #impl_generics Value for #value_enum_ident #ty_generics {
+            impl #enum_impl_generics Value for #value_enum_ident #enum_ty_generics #enum_where_clause {
                 fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
                     match self {
-                        #(#value_enum_ident::#newtype_value_variants(inner) => inner.dehydrate(x), )*
-                        _ => unreachable!(),
+                        #(#value_enum_ident::#data_value_variants(inner) => inner.dehydrate(x), )*
+                        #(#value_enum_ident::#tagged_variants => {
+                            *x.first_mut()? = #variant_tags;
+                            Some(1)
+                        },)*
+                    }
+                }
+
+                fn dehydrated_len(&self) -> usize {
+                    match self {
+                        #(#value_enum_ident::#data_value_variants(inner) => inner.dehydrated_len(), )*
+                        #(#value_enum_ident::#tagged_variants => 1,)*
                     }
                 }
 
                 fn as_float(&self) -> Option<f32> {
                     match self {
-                        #(#value_enum_ident::#newtype_value_variants(inner) => inner.as_float(), )*
+                        #(#value_enum_ident::#data_value_variants(inner) => inner.as_float(), )*
                         _ => None,
                     }
                 }
 
-                fn parse_value<T: Sized>(self, x: &str) -> Option<T> {
+                fn parse_value<__T: 'static>(self, x: &str) -> Option<__T> {
                     match &x[..] {
-                        #(s if s.starts_with(#newtype_names_string) => {
+                        #(s if FieldsType::starts_with_segment(s, #parsed_data_names_string) => {
                             return match self {
-                                #value_enum_ident::#newtype_value_variants(x) => x.parse_value(&s[#newtype_names_string.len()..]),
+                                #value_enum_ident::#parsed_data_value_variants(x) => x.parse_value(&s[#parsed_data_names_string.len()..]),
                                 _ => None
                             }
                         },)*
@@ -465,24 +1124,31 @@ impl Receiver {
     }
 }
 
-#[derive(FromField, Clone)]
-#[darling(attributes(variant_names))]
-struct ReceiverFieldVar {
-    ty: Type
-}
-
-
 #[derive(FromVariant, Clone)]
-#[darling(attributes(variant_names))]
+#[darling(attributes(remote), forward_attrs(doc))]
 #[allow(dead_code)]
 struct ReceiverVariant {
     ident: Ident,
-    fields: Fields<ReceiverFieldVar>,
+    fields: Fields<ReceiverField>,
+    attrs: Vec<Attribute>,
     #[darling(default)]
     skip: bool,
     #[darling(default)]
     write_only: bool,
     #[darling(default)]
     read_only: bool,
+    #[darling(default)]
+    rename: Option<String>,
+    #[darling(default)]
+    id: Option<u32>,
+    #[darling(default)]
+    unit: Option<String>,
+    #[darling(default)]
+    description: Option<String>,
+    /// other paths the variant is parsed from, without the leading `::`
+    #[darling(default, multiple)]
+    alias: Vec<String>,
+    #[darling(default)]
+    deprecated: bool,
 }
 
@@ -1,34 +1,71 @@
 use proc_macro2::Ident;
 use quote::{format_ident, quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
-use syn::{Expr, token, bracketed, Type};
+use darling::FromMeta;
+use syn::{Expr, ExprLit, Lit, token, bracketed, Type, Member, Index, LitInt, LitFloat, AngleBracketedGenericArguments};
 
 extern crate proc_macro2;
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 enum IdentOrIndex {
-    Field(Ident),
+    Field(Member),
     Variant(Ident),
     Index(Expr),
 }
 
+/// Parses the segment following a `.`, either a field name or tuple indices (`.0.1` is lexed as
+/// a single float literal, so it is split back into two indices here).
+fn parse_field(input: ParseStream, path: &mut Vec<IdentOrIndex>) -> syn::Result<()> {
+    let lookahead = input.lookahead1();
+    if lookahead.peek(syn::Ident) {
+        path.push(IdentOrIndex::Field(Member::Named(input.parse::<Ident>()?)));
+    } else if lookahead.peek(LitInt) {
+        path.push(IdentOrIndex::Field(Member::Unnamed(input.parse::<Index>()?)));
+    } else if lookahead.peek(LitFloat) {
+        let lit = input.parse::<LitFloat>()?;
+        for index in lit.to_string().split('.') {
+            let index = index.parse::<u32>()
+                .map_err(|_| syn::Error::new(lit.span(), "expected tuple index"))?;
+            path.push(IdentOrIndex::Field(Member::Unnamed(Index { index, span: lit.span() })));
+        }
+    } else {
+        return Err(lookahead.error())
+    }
+    Ok(())
+}
+
+/// Generic arguments of the base type, given turbofish style as in `Bank::<4>.slots[0]`.
+fn parse_generic_args(input: ParseStream) -> syn::Result<Option<AngleBracketedGenericArguments>> {
+    if input.peek(syn::Token![::]) && input.peek3(syn::Token![<]) {
+        Ok(Some(input.parse()?))
+    } else {
+        Ok(None)
+    }
+}
+
 pub(crate) struct Setter {
     path: Vec<IdentOrIndex>,
     base_type: Ident,
-    expr: Option<Expr>
+    generic_args: Option<AngleBracketedGenericArguments>,
+    expr: Option<Expr>,
+    /// `Type.path reset`, resetting the value at the path to its default
+    reset: bool,
 }
 
 impl Parse for Setter {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let base_type = input.parse::<Ident>()?;
+        let generic_args = parse_generic_args(input)?;
         let mut path = Vec::new();
         let mut expr = None;
+        let mut reset = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(syn::Token![.]) {
                 input.parse::<syn::Token![.]>()?;
-                path.push(IdentOrIndex::Field(input.parse::<Ident>()?));
+                parse_field(input, &mut path)?;
             } else if lookahead.peek(syn::Token![::]) {
                 input.parse::<syn::Token![::]>()?;
                 path.push(IdentOrIndex::Variant(input.parse::<Ident>()?));
@@ -40,14 +77,23 @@ impl Parse for Setter {
                 input.parse::<syn::Token![=]>()?;
                 expr = Some(input.parse::<Expr>()?);
                 break;
+            } else if lookahead.peek(syn::Ident) {
+                let ident = input.parse::<Ident>()?;
+                if ident != "reset" {
+                    return Err(syn::Error::new(ident.span(), "expected `reset`"));
+                }
+                reset = true;
+                break;
             } else {
                 return Err(lookahead.error())
             }
         }
 
-        if expr.is_none() {
-            match path.last().unwrap() {
-                IdentOrIndex::Variant(_) => {},
+        // unit variants and `.pop` are set without `= expr`
+        if expr.is_none() && !reset {
+            match path.last() {
+                Some(IdentOrIndex::Variant(_)) => {},
+                Some(IdentOrIndex::Field(Member::Named(ident))) if ident == "pop" => {},
                 _ => return Err(input.error("expected `=`"))
             }
         }
@@ -55,29 +101,53 @@ impl Parse for Setter {
         Ok(Setter {
             path,
             base_type,
-            expr
+            generic_args,
+            expr,
+            reset
         })
     }
 }
 
 impl ToTokens for Setter {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let base_setter_type = format_ident!("{}Setter", self.base_type);
+        let base_type = &self.base_type;
+        let base_setter_type = match &self.generic_args {
+            Some(args) => quote!(<#base_type #args as RemoteSet>::SetterType),
+            None => format_ident!("{}Setter", base_type).into_token_stream(),
+        };
         let expr = self.expr.clone();
 
+        let mut path = &self.path[..];
         let mut partial;
         match expr {
+            // fields are reset by their container, anything else resets itself
+            None if self.reset => match path.split_last() {
+                Some((IdentOrIndex::Field(i), rest)) => {
+                    let i = format_ident!("reset_{}", i);
+                    partial = quote!{x.#i()};
+                    path = rest;
+                }
+                _ => {
+                    partial = quote!{x.reset()};
+                }
+            },
             None => {
                 partial = quote!{()};
             }
             Some(expr) => {
-                partial = quote!{#expr};
+                partial = quote!{SetterFrom::setter_from(#expr)};
             }
         }
 
-        for i in self.path.iter().rev() {
+        for i in path.iter().rev() {
             match i {
-                IdentOrIndex::Field(i) | IdentOrIndex::Variant(i) => {
+                IdentOrIndex::Field(i) => {
+                    let i = format_ident!("make_{}", i);
+                    partial = quote! {
+                        x.#i(|x| #partial)
+                    };
+                }
+                IdentOrIndex::Variant(i) => {
                     let i = format_ident!("make_{}", i);
                     partial = quote! {
                         x.#i(|x| #partial)
@@ -104,18 +174,20 @@ impl ToTokens for Setter {
 pub(crate) struct Getter {
     path: Vec<IdentOrIndex>,
     base_type: Ident,
+    generic_args: Option<AngleBracketedGenericArguments>,
 }
 
 impl Parse for Getter {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let base_type = input.parse::<Ident>()?;
+        let generic_args = parse_generic_args(input)?;
         let mut path = Vec::new();
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(syn::Token![.]) {
                 input.parse::<syn::Token![.]>()?;
-                path.push(IdentOrIndex::Field(input.parse::<Ident>()?));
+                parse_field(input, &mut path)?;
             } else if lookahead.peek(syn::Token![::]) {
                 input.parse::<syn::Token![::]>()?;
                 path.push(IdentOrIndex::Variant(input.parse::<Ident>()?));
@@ -131,18 +203,29 @@ impl Parse for Getter {
         Ok(Getter {
             path,
             base_type,
+            generic_args,
         })
     }
 }
 
 impl ToTokens for Getter {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let base_getter_type = format_ident!("{}Getter", self.base_type);
+        let base_type = &self.base_type;
+        let base_getter_type = match &self.generic_args {
+            Some(args) => quote!(<#base_type #args as RemoteGet>::GetterType),
+            None => format_ident!("{}Getter", base_type).into_token_stream(),
+        };
 
         let mut partial = quote!{NullGetter{}};
         for i in self.path.iter().rev() {
             match i {
-                IdentOrIndex::Field(i) | IdentOrIndex::Variant(i) => {
+                IdentOrIndex::Field(i) => {
+                    let i = format_ident!("make_{}", i);
+                    partial = quote! {
+                        x.#i(|x| #partial)
+                    };
+                }
+                IdentOrIndex::Variant(i) => {
                     let i = format_ident!("make_{}", i);
                     partial = quote! {
                         x.#i(|x| #partial)
@@ -166,10 +249,67 @@ impl ToTokens for Getter {
     }
 }
 
+/// An expression given in an attribute. Attributes only take literals, so other expressions
+/// (including negative numbers) are written as strings (`min = "-1.0"`), which are parsed.
+#[derive(Clone)]
+pub(crate) struct AttrExpr(pub(crate) Expr);
+
+impl FromMeta for AttrExpr {
+    fn from_value(value: &Lit) -> darling::Result<Self> {
+        match value {
+            Lit::Str(s) => s.parse().map(AttrExpr).map_err(darling::Error::from),
+            lit => Ok(AttrExpr(Expr::Lit(ExprLit { attrs: Vec::new(), lit: lit.clone() }))),
+        }
+    }
+}
+
 pub(crate) fn strip_ref(ty: Type) -> Type {
     if let Type::Reference(ref ty) = ty {
         strip_ref(*ty.elem.clone())
     } else {
         ty
     }
-}
\ No newline at end of file
+}
+
+/// Type as written in the source, e.g. `Option<[u8; 4]>`, with the spacing of `to_string` removed
+/// except between words and after `,` and `;`.
+pub(crate) fn type_name(ty: &Type) -> String {
+    let tokens = quote::quote!(#ty).to_string();
+    let mut name = String::new();
+    let mut chars = tokens.chars().peekable();
+    while let Some(c) = chars.next() {
+        let word = |c: char| c.is_alphanumeric() || c == '_';
+        let keep = match (name.chars().last(), chars.peek()) {
+            (Some(prev), Some(&next)) => (word(prev) && word(next)) || prev == ',' || prev == ';',
+            _ => false,
+        };
+        if c != ' ' || keep {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// Name of a struct field as used in paths, `a` for named fields and `0` for tuple fields.
+pub(crate) fn field_name(ident: &Option<Ident>, index: usize) -> String {
+    match ident {
+        Some(ident) => ident.to_string(),
+        None => index.to_string(),
+    }
+}
+
+/// Expression used to access a struct field, `self.#member`.
+pub(crate) fn field_member(ident: &Option<Ident>, index: usize) -> Member {
+    match ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    }
+}
+
+/// Variant of the generated enums for a struct field, tuple fields are prefixed with `_`.
+pub(crate) fn field_variant(ident: &Option<Ident>, index: usize) -> Ident {
+    match ident {
+        Some(ident) => ident.clone(),
+        None => format_ident!("_{}", index),
+    }
+}
@@ -0,0 +1,23 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+/// `compile_error!` if two fields or variants of a container share the same `#[remote(id = ..)]`.
+pub(crate) fn check_duplicate_ids(ids: impl IntoIterator<Item = Option<u32>>) -> TokenStream {
+    let mut seen = Vec::new();
+    for id in ids.into_iter().flatten() {
+        if seen.contains(&id) {
+            let msg = format!("duplicate remote id `{:#x}`", id);
+            return quote!(compile_error!(#msg););
+        }
+        seen.push(id);
+    }
+    quote!()
+}
+
+/// The variants of a generated enum which have an id, together with their ids.
+pub(crate) fn with_ids<'a>(variants: impl IntoIterator<Item = (&'a Ident, Option<u32>)>) -> (Vec<Ident>, Vec<u32>) {
+    variants.into_iter()
+        .filter_map(|(variant, id)| Some((variant.clone(), id?)))
+        .unzip()
+}
@@ -8,6 +8,13 @@ use quote::{quote, ToTokens};
 mod setter;
 mod getter;
 mod helper;
+mod generics;
+mod rename;
+mod range;
+mod id;
+mod flatten;
+mod alias;
+mod proxy;
 
 #[proc_macro_derive(RemoteSetter, attributes(remote))]
 pub fn derive_setter(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
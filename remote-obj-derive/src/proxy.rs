@@ -0,0 +1,116 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Member, Path, Type};
+use crate::helper::{field_member, strip_ref};
+
+/// Type a field of type `ty` is read and written through (see `Proxy`), the mirror given with
+/// `#[remote(with = "..")]`, or `Opaque<ty>` for `#[remote(opaque)]` fields. `None` if the field
+/// is accessed directly.
+pub(crate) fn proxy_type(ty: &Type, opaque: bool, with: &Option<Type>) -> Option<Type> {
+    let ty = strip_ref(ty.clone());
+    with.clone().or_else(|| opaque.then(|| parse_quote!(Opaque<#ty>)))
+}
+
+/// Type implementing `RemoteGet`/`RemoteSet` for the field, the proxy if it has one.
+pub(crate) fn access_type(ty: &Type, proxy: &Option<Type>) -> Type {
+    match proxy {
+        Some(proxy) => proxy.clone(),
+        None => strip_ref(ty.clone()),
+    }
+}
+
+/// Reference to the field at `value`, a `&ty`, as a reference to its access type, converted with
+/// `Proxy` or, with `mutable`, `ProxyMut`.
+pub(crate) fn read(ty: &Type, proxy: &Option<Type>, value: TokenStream, mutable: bool) -> TokenStream {
+    let ty = strip_ref(ty.clone());
+    let proxy_trait = if mutable { quote!(ProxyMut) } else { quote!(Proxy) };
+    match proxy {
+        Some(proxy) => quote!(&<#proxy as #proxy_trait<#ty>>::from_field(#value)),
+        None => value,
+    }
+}
+
+/// Evaluates `write` with a mutable reference to the field at `target`, a `&mut ty`, as its
+/// access type. Proxies are written back into the field afterwards.
+pub(crate) fn write(ty: &Type, proxy: &Option<Type>, target: TokenStream, write: impl FnOnce(TokenStream) -> TokenStream) -> TokenStream {
+    let ty = strip_ref(ty.clone());
+    match proxy {
+        Some(proxy) => {
+            let write = write(quote!((&mut proxy)));
+            quote! {{
+                let field: &mut #ty = #target;
+                let mut proxy = <#proxy as ProxyMut<#ty>>::from_field(field);
+                let result = #write;
+                *field = proxy.into_field();
+                result
+            }}
+        }
+        None => write(target),
+    }
+}
+
+/// Fields of a struct or variant of a `#[remote(remote = "..")]` mirror, as `(ident, index)`.
+pub(crate) type MirrorFields = Vec<(Option<Ident>, usize)>;
+
+/// Pattern binding all fields of the struct or variant `path` to `__field_<member>`, and the
+/// bindings.
+fn bind(path: TokenStream, fields: &MirrorFields) -> (TokenStream, Vec<Ident>) {
+    let members: Vec<_> = fields.iter().map(|(ident, index)| field_member(ident, *index)).collect();
+    let bindings: Vec<_> = members.iter().map(|member| match member {
+        Member::Named(ident) => format_ident!("__field_{}", ident),
+        Member::Unnamed(index) => format_ident!("__field_{}", index.index),
+    }).collect();
+    (quote!(#path { #(#members: #bindings),* }), bindings)
+}
+
+/// `Proxy` (or with `mutable`, `ProxyMut`) impl of a mirror of the foreign type `remote`,
+/// converting between the two field by field. `variants` are the names and fields of the
+/// variants of an enum, or a single `None` with the fields of a struct. All fields of `remote`
+/// have to be listed in the mirror, and be `Clone`: every access clones all of them.
+pub(crate) fn impl_mirror(remote: &Path, ident: &Ident, variants: &[(Option<Ident>, MirrorFields)], mutable: bool) -> TokenStream {
+    let mut from_arms = Vec::new();
+    let mut into_arms = Vec::new();
+    for (variant, fields) in variants {
+        let (remote_path, mirror_path) = match variant {
+            Some(variant) => (quote!(#remote::#variant), quote!(#ident::#variant)),
+            None => (quote!(#remote), quote!(#ident)),
+        };
+        // the same tokens are used as the pattern and the expression
+        let (remote_fields, bindings) = bind(remote_path, fields);
+        let (mirror_fields, _) = bind(mirror_path, fields);
+        from_arms.push(quote!(#remote_fields => {
+            #(let #bindings = Clone::clone(#bindings);)*
+            #mirror_fields
+        }));
+        into_arms.push(quote!(#mirror_fields => #remote_fields,));
+    }
+
+    if !mutable {
+        return quote! {
+            #[automatically_derived]
+            impl Proxy<#remote> for #ident {
+                fn from_field(x: &#remote) -> Self {
+                    match x {
+                        #(#from_arms)*
+                    }
+                }
+            }
+        };
+    }
+    quote! {
+        #[automatically_derived]
+        impl ProxyMut<#remote> for #ident {
+            fn from_field(x: &#remote) -> Self {
+                match x {
+                    #(#from_arms)*
+                }
+            }
+
+            fn into_field(self) -> #remote {
+                match self {
+                    #(#into_arms)*
+                }
+            }
+        }
+    }
+}
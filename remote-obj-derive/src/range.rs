@@ -0,0 +1,47 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, Type};
+use crate::helper::AttrExpr;
+
+/// `#[remote(range(min = 0.0, max = 100.0))]` on a numeric field, `clamp` saturates out of range
+/// values instead of rejecting them.
+#[derive(FromMeta, Clone)]
+pub(crate) struct Range {
+    min: AttrExpr,
+    max: AttrExpr,
+    #[darling(default)]
+    clamp: bool,
+}
+
+/// The bounds are typed as the field rather than cast to it, so bounds that don't fit the field
+/// (e.g. `max = 10.9` on a `u8`) are compile errors instead of being truncated.
+impl Range {
+    /// Expression checking the setter `x` of a field of type `ty`, evaluating to
+    /// `Result<#ty, Error>`.
+    pub(crate) fn check(&self, ty: &Type) -> TokenStream {
+        let (min, max, clamp) = (&self.min.0, &self.max.0, self.clamp);
+        quote!(ValueRange::check::<#ty>(x, #min, #max, #clamp))
+    }
+
+    /// Item failing to compile if `default`, the `#[remote(default = ..)]` of a field of type
+    /// `ty`, is outside of the bounds.
+    pub(crate) fn check_default(&self, ty: &Type, default: &Expr) -> TokenStream {
+        let (min, max) = (&self.min.0, &self.max.0);
+        quote! {
+            const _: () = {
+                let (default, min, max): (#ty, #ty, #ty) = (#default, #min, #max);
+                assert!(default >= min && default <= max, "`#[remote(default = ..)]` is outside of `#[remote(range(..))]`");
+            };
+        }
+    }
+
+    /// Expression evaluating to the `ValueRange` of a field of type `ty`.
+    pub(crate) fn metadata(&self, ty: &Type) -> TokenStream {
+        let (min, max) = (&self.min.0, &self.max.0);
+        quote!({
+            let (min, max): (#ty, #ty) = (#min, #max);
+            ValueRange { min: min as f64, max: max as f64 }
+        })
+    }
+}
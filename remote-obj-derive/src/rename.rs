@@ -0,0 +1,101 @@
+use darling::FromMeta;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+/// Case conversion of `#[remote(rename_all = "...")]`, applied to the fields of a struct or the
+/// variants of an enum. Uses the same names as serde.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(match value {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return Err(darling::Error::unknown_value(value)),
+        })
+    }
+}
+
+/// Splits a `snake_case` or `PascalCase` identifier into lowercase words.
+fn words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c == '_' {
+            words.push(String::new());
+            prev_lower = false;
+            continue;
+        }
+        if words.is_empty() || (c.is_uppercase() && prev_lower) {
+            words.push(String::new());
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        words.last_mut().unwrap().extend(c.to_lowercase());
+    }
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl RenameRule {
+    pub(crate) fn apply(self, name: &str) -> String {
+        let words = words(name);
+        match self {
+            RenameRule::Lower => words.concat(),
+            RenameRule::Upper => words.concat().to_uppercase(),
+            RenameRule::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+            RenameRule::Camel => words.iter().enumerate().map(|(i, w)| {
+                if i == 0 { w.clone() } else { capitalize(w) }
+            }).collect(),
+            RenameRule::Snake => words.join("_"),
+            RenameRule::ScreamingSnake => words.join("_").to_uppercase(),
+            RenameRule::Kebab => words.join("-"),
+            RenameRule::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+/// Name of a field or variant as used in paths (without the leading `.` or `::`), `rename` takes
+/// precedence over `rename_all`. Tuple fields are named by their index.
+pub(crate) fn path_name(ident: &Option<Ident>, index: usize, rename: &Option<String>, rename_all: Option<RenameRule>) -> String {
+    match (rename, ident, rename_all) {
+        (Some(rename), _, _) => rename.clone(),
+        (None, Some(ident), Some(rule)) => rule.apply(&ident.to_string()),
+        (None, Some(ident), None) => ident.to_string(),
+        (None, None, _) => index.to_string(),
+    }
+}
+
+/// `compile_error!` if two fields or variants share the same path, e.g. after renaming.
+pub(crate) fn check_duplicates(paths: &[String]) -> TokenStream {
+    for (i, path) in paths.iter().enumerate() {
+        if paths[..i].contains(path) {
+            let msg = format!("duplicate remote path `{}`", path);
+            return quote!(compile_error!(#msg););
+        }
+    }
+    quote!()
+}
@@ -1,13 +1,20 @@
 use darling::{ast::Data, FromDeriveInput, FromField, FromVariant};
 use darling::ast::Fields;
-use darling::util::PathList;
+use darling::util::{Ignored, PathList};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::{Generics, Ident, Type, Visibility};
-use crate::helper::strip_ref;
+use syn::{Attribute, Generics, Ident, Path, Type, Visibility};
+use crate::alias::{parsed_paths, paths, pick};
+use crate::flatten::{cases, check_flatten, field_arm};
+use crate::generics::{container_generics, enum_generics, impl_copy, impl_default, mentions_params};
+use crate::helper::{field_member, field_name, field_variant, strip_ref, AttrExpr};
+use crate::id::{check_duplicate_ids, with_ids};
+use crate::proxy::{access_type, impl_mirror, proxy_type, MirrorFields, read, write};
+use crate::range::Range;
+use crate::rename::{check_duplicates, path_name, RenameRule};
 
 #[derive(FromDeriveInput)]
-#[darling(supports(struct_named, enum_any), forward_attrs(derive), attributes(remote))]
+#[darling(supports(struct_any, enum_any), forward_attrs(derive), attributes(remote))]
 pub(crate) struct Receiver {
     ident: Ident,
     generics: Generics,
@@ -15,6 +22,25 @@ pub(crate) struct Receiver {
     vis: Visibility,
     #[darling(default)]
     derive: PathList,
+    /// adds a setter replacing the whole enum, requires the enum to be `Copy`
+    #[darling(default)]
+    replace: bool,
+    /// case conversion of the paths of fields (structs) or variants (enums), e.g. `camelCase`
+    #[darling(default)]
+    rename_all: Option<RenameRule>,
+    /// `fn(&Self, &Self::SetterType) -> Result<(), Error>` called before every write
+    #[darling(default)]
+    validate: Option<Path>,
+    /// `fn(&mut Self)` called after every successful write
+    #[darling(default)]
+    on_set: Option<Path>,
+    /// read-only, see the getter
+    #[allow(dead_code)]
+    #[darling(default, multiple)]
+    computed: Vec<Ignored>,
+    /// foreign type this is a mirror of, see `impl_mirror`
+    #[darling(default)]
+    remote: Option<Path>,
 }
 
 impl ToTokens for Receiver {
@@ -27,22 +53,201 @@ impl ToTokens for Receiver {
                 self.to_tokens_struct(tokens);
             }
         }
+        if let Some(remote) = &self.remote {
+            tokens.extend(impl_mirror(remote, &self.ident, &self.mirror_fields(), true));
+        }
+    }
+}
+
+impl Receiver {
+    /// All fields of the struct, or of each of the variants of the enum, see `impl_mirror`.
+    fn mirror_fields(&self) -> Vec<(Option<Ident>, MirrorFields)> {
+        let fields = |fields: &Fields<ReceiverField>| -> MirrorFields {
+            fields.iter().enumerate().map(|(index, field)| (field.ident.clone(), index)).collect()
+        };
+        match &self.data {
+            Data::Enum(variants) => variants.iter().map(|v| (Some(v.ident.clone()), fields(&v.fields))).collect(),
+            Data::Struct(struct_fields) => vec![(None, fields(struct_fields))],
+        }
     }
 }
 
 impl Receiver {
     fn setter_fields_to_emit(&self) -> Vec<ReceiverField> {
-        self.data
-            .as_ref()
-            .take_struct()
-            .expect("FieldNames only supports named structs")
-            .into_iter()
-            .filter(|field| {
-                !field.skip && !field.read_only
-            } )
-            .map(|x| (*x).clone())
-            .collect()
+        fields_to_emit(
+            &self.data
+                .as_ref()
+                .take_struct()
+                .expect("FieldNames only supports structs")
+                .map(|x| x.clone())
+        )
+    }
+}
+
+/// Path of a field, e.g. `.a`, see `path_name`. `#[remote(flatten)]` fields have an empty path.
+fn field_path(field: &ReceiverField, rename_all: Option<RenameRule>) -> String {
+    if field.flatten {
+        return String::new();
+    }
+    format!(".{}", path_name(&field.ident, field.index, &field.rename, rename_all))
+}
+
+/// Expression writing the setter `x` into the field at `target`, a `&mut`, checking
+/// `#[remote(range(..))]` first.
+fn set_field(field: &ReceiverField, target: TokenStream) -> TokenStream {
+    let access = field.access_type();
+    let set = |target| quote!(<#access as RemoteSet>::set(#target, x).map_err(Error::nested));
+    match &field.range {
+        Some(range) => {
+            let check = range.check(&strip_ref(field.ty.clone()));
+            let set = write(&field.ty, &field.proxy(), target, set);
+            quote!(#check.and_then(|x| #set))
+        }
+        None => write(&field.ty, &field.proxy(), target, set),
+    }
+}
+
+/// Expression resetting the field at `target`, a `&mut`, to its `#[remote(default = ..)]`, or
+/// recursively if it has none, evaluating to `Result<(), Error>`.
+fn reset_field(field: &ReceiverField, target: TokenStream) -> TokenStream {
+    let ty = strip_ref(field.ty.clone());
+    let access = field.access_type();
+    match &field.default {
+        Some(AttrExpr(default)) => quote! {{
+            let field: &mut #ty = #target;
+            *field = #default;
+            Ok(())
+        }},
+        None => {
+            let reset = write(&field.ty, &field.proxy(), target, |target| quote!(<#access as RemoteSet>::reset_to_default(#target)));
+            quote!(#reset.map_err(Error::nested))
+        }
+    }
+}
+
+/// Expression running all of `resets`, evaluating to the first error.
+fn reset_all(resets: &[TokenStream]) -> TokenStream {
+    quote! {{
+        let result: Result<(), Error> = Ok(());
+        #(let result = result.and(#resets);)*
+        result
+    }}
+}
+
+/// Statement passing the leaves of the field `name` at `value` which differ from `other`, or from
+/// the value the field is reset to if there is no `other`, to `changed`.
+fn diff_field(field: &ReceiverField, name: &str, value: TokenStream, other: Option<TokenStream>) -> TokenStream {
+    let ty = strip_ref(field.ty.clone());
+    let access = field.access_type();
+    let proxy = field.proxy();
+    let value = read(&ty, &proxy, value, true);
+    match (other, &field.default) {
+        (Some(other), _) => {
+            let other = read(&ty, &proxy, other, true);
+            quote!(<#access as RemoteSet>::diff(#value, #other, &path.field(#name), changed);)
+        }
+        (None, Some(AttrExpr(default))) => {
+            let default_value = read(&ty, &proxy, quote!(&default), true);
+            quote! {{
+                let default: #ty = #default;
+                <#access as RemoteSet>::diff(#value, #default_value, &path.field(#name), changed);
+            }}
+        }
+        (None, None) => quote!(<#access as RemoteSet>::diff_default(#value, &path.field(#name), changed);),
+    }
+}
+
+/// Arm matching the `__ResetField` setter `pattern`, evaluating to `arms[i]` for the field `i`
+/// and to `fallback` for indices of no field.
+fn reset_field_arm(pattern: TokenStream, arms: &[TokenStream], fallback: TokenStream) -> TokenStream {
+    if arms.is_empty() {
+        return quote!(#pattern(_) => #fallback);
     }
+    let indices: Vec<_> = (0..arms.len() as u32).collect();
+    quote! {
+        #pattern(i) => match i {
+            #(#indices => #arms,)*
+            _ => #fallback
+        }
+    }
+}
+
+/// Arms of `set` for the `__Reset` and `__ResetField` setters of `to_tokens_fields`, with
+/// `resets` resetting each of the fields.
+fn reset_arms(setter_enum_ident: &Ident, resets: &[TokenStream]) -> TokenStream {
+    let field_arm = reset_field_arm(quote!(#setter_enum_ident::__ResetField), resets, quote!(Err(Error::EmptyPath { depth: 0 })));
+    let reset_all = reset_all(resets);
+    quote! {
+        #setter_enum_ident::__Reset => #reset_all,
+        #field_arm,
+    }
+}
+
+/// Calls of `#[remote(validate = "..")]` and `#[remote(on_set = "..")]`, wrapped around the
+/// write in `set`. Validators get the setter as received, before `#[remote(range(..))]` checks.
+/// The paths are string literals, since syn 1 only parses literals as attribute values.
+#[derive(Default)]
+struct Hooks {
+    validate: Vec<TokenStream>,
+    on_set: Vec<TokenStream>,
+}
+
+impl Hooks {
+    /// Adds the hooks of `field`, which is written by the setters matching `pattern(binding)` and
+    /// reset when `reset` evaluates to true. Errors of validators of fields inside enum variants
+    /// are nested under the variant. Resets only call `on_set`, as there is no setter to validate.
+    fn add_field(&mut self, field: &ReceiverField, pattern: impl Fn(TokenStream) -> TokenStream, reset: TokenStream, nested: bool) {
+        if let Some(validate) = &field.validate {
+            let pattern = pattern(quote!(x));
+            let nest = if nested { quote!(.map_err(Error::nested)) } else { quote!() };
+            self.validate.push(quote! {
+                if let #pattern = x {
+                    #validate(self, &x)#nest?;
+                }
+            });
+        }
+        if let Some(on_set) = &field.on_set {
+            let pattern = pattern(quote!(_));
+            self.on_set.push(quote! {
+                if matches!(x, #pattern) || #reset {
+                    #on_set(self);
+                }
+            });
+        }
+    }
+
+    /// Body of `set`, with `write` evaluating to the result of the write.
+    fn wrap(mut self, receiver: &Receiver, write: TokenStream) -> TokenStream {
+        if let Some(validate) = &receiver.validate {
+            self.validate.insert(0, quote!(#validate(self, &x)?;));
+        }
+        if let Some(on_set) = &receiver.on_set {
+            self.on_set.push(quote!(#on_set(self);));
+        }
+        if self.validate.is_empty() && self.on_set.is_empty() {
+            return write;
+        }
+        let (validate, on_set) = (self.validate, self.on_set);
+        quote! {
+            #(#validate)*
+            let result = #write;
+            if result.is_ok() {
+                #(#on_set)*
+            }
+            result
+        }
+    }
+}
+
+fn fields_to_emit(fields: &Fields<ReceiverField>) -> Vec<ReceiverField> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| {
+            !field.skip && !field.read_only
+        } )
+        .map(|(index, x)| ReceiverField { index, ..x.clone() })
+        .collect()
 }
 
 impl Receiver {
@@ -50,53 +255,226 @@ impl Receiver {
         let ident = &self.ident;
         let setter_enum_ident = format_ident!("{}Setter", ident);
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
         let fields = self.setter_fields_to_emit();
+        let struct_fields = self.data.as_ref().take_struct().expect("FieldNames only supports structs");
+        tokens.extend(check_duplicate_ids(struct_fields.iter().map(|field| field.id)));
 
-        // panic!("{:?}, {:?}, {:?}", impl_generics, ty_generics, where_clause);
+        if self.replace {
+            tokens.extend(quote!(compile_error!("`#[remote(replace)]` is only supported on enums");));
+        }
+
+        let enum_generics = self.to_tokens_fields(tokens, &setter_enum_ident, &fields, self.rename_all);
+        let (_, enum_ty_generics, _) = enum_generics.split_for_impl();
 
-        let types: Vec<_> = fields.iter().map(|field|
-            strip_ref(field.ty.clone())
+        let types: Vec<_> = fields.iter().map(ReceiverField::access_type).collect();
+        let generics = container_generics(&self.generics, &types, quote!(RemoteSet));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let names: Vec<_> = fields.iter().map(|field|
+            field_variant(&field.ident, field.index)
         ).collect();
 
-        let names: Vec<_> = fields.clone().into_iter().map(|field|
-            field.ident.unwrap()
+        let set_fields: Vec<_> = fields.iter().map(|field| {
+            let member = field_member(&field.ident, field.index);
+            set_field(field, quote!(&mut self.#member))
+        }).collect();
+
+        let resets: Vec<_> = fields.iter().map(|field| {
+            let member = field_member(&field.ident, field.index);
+            reset_field(field, quote!(&mut self.#member))
+        }).collect();
+        let reset_arms = reset_arms(&setter_enum_ident, &resets);
+
+        let paths: Vec<_> = fields.iter().map(|field| field_path(field, self.rename_all)).collect();
+        let diffs: Vec<_> = fields.iter().zip(paths.iter()).map(|(field, path)| {
+            let member = field_member(&field.ident, field.index);
+            diff_field(field, path, quote!(&self.#member), Some(quote!(&other.#member)))
+        }).collect();
+        let diff_defaults: Vec<_> = fields.iter().zip(paths.iter()).map(|(field, path)| {
+            let member = field_member(&field.ident, field.index);
+            diff_field(field, path, quote!(&self.#member), None)
+        }).collect();
+
+        let mut hooks = Hooks::default();
+        for ((field, name), index) in fields.iter().zip(names.iter()).zip(0u32..) {
+            let reset = quote!(matches!(x, #setter_enum_ident::__Reset | #setter_enum_ident::__ResetField(#index)));
+            hooks.add_field(field, |x| quote!(#setter_enum_ident::#name(#x)), reset, false);
+        }
+        let body = hooks.wrap(self, quote! {
+            match x {
+                #(#setter_enum_ident::#names(x) => #set_fields,)*
+                #reset_arms
+                #setter_enum_ident::__None => Err(Error::EmptyPath { depth: 0 })
+            }
+        });
+
+        tokens.extend(quote! {
+            #[allow(non_snake_case)]
+            impl #impl_generics RemoteSet for #ident #ty_generics #where_clause {
+                type SetterType = #setter_enum_ident #enum_ty_generics;
+
+                fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+                    #body
+                }
+
+                // through `set`, to call the hooks
+                fn reset_to_default(&mut self) -> Result<(), Error> {
+                    RemoteSet::set(self, #setter_enum_ident::__Reset)
+                }
+
+                #[allow(unused_variables)]
+                fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+                    #(#diffs)*
+                }
+
+                #[allow(unused_variables)]
+                fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+                    #(#diff_defaults)*
+                }
+            }
+        })
+    }
+
+    /// Emits the setter enum for a list of fields, used for structs and for the fields of
+    /// struct-like and tuple enum variants. Returns the generics of the emitted enum.
+    fn to_tokens_fields(&self, tokens: &mut TokenStream, setter_enum_ident: &Ident, fields: &[ReceiverField], rename_all: Option<RenameRule>) -> Generics {
+        let types: Vec<_> = fields.iter().map(ReceiverField::access_type).collect();
+
+        let generics = enum_generics(&self.generics, &types, quote!(RemoteSet), false);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let setter_types: Vec<_> = types.iter().map(|ty| quote!(<#ty as RemoteSet>::SetterType)).collect();
+
+        let names: Vec<_> = fields.iter().map(|field|
+            field_variant(&field.ident, field.index)
         ).collect();
 
-        let method_names: Vec<_> = fields.clone().into_iter().map(|field| {
-            format_ident!("make_{}", field.ident.unwrap())
+        let method_names: Vec<_> = fields.iter().map(|field| {
+            format_ident!("make_{}", field_name(&field.ident, field.index))
         }).collect();
         let vis = &self.vis;
         let inner_derives = &self.derive;
 
-        let names_string: Vec<String> = fields.clone().into_iter().map(|field| {
-            format!(".{}", field.ident.unwrap())
+        let names_string: Vec<String> = fields.iter().map(|field| field_path(field, rename_all)).collect();
+        // aliases are parsed like the canonical paths, but not displayed, flattened fields have none
+        let aliases: Vec<&[String]> = fields.iter().map(|field| {
+            if field.flatten { &[][..] } else { &field.alias[..] }
         }).collect();
+        let parsed = parsed_paths(&names_string, &aliases, ".");
+        tokens.extend(check_duplicates(&paths(&parsed)));
+        let parsed_names = pick(&names, &parsed);
+        let parsed_types = pick(&types, &parsed);
+
+        let flattened: Vec<_> = fields.iter().zip(types.iter()).map(|(field, ty)| {
+            field.flatten.then(|| quote!(<<#ty as RemoteSet>::SetterType as Setter>::FIELDS))
+        }).collect();
+        for ((field, ty), flattened) in fields.iter().zip(types.iter()).zip(flattened.iter()) {
+            if let Some(flattened) = flattened {
+                tokens.extend(check_flatten(&self.generics, ty, flattened));
+            }
+            let ty = strip_ref(field.ty.clone());
+            if let (Some(range), Some(AttrExpr(default))) = (&field.range, &field.default) {
+                if !mentions_params(&self.generics, &ty) {
+                    tokens.extend(range.check_default(&ty, default));
+                }
+            }
+        }
+        let (guards, rests): (Vec<_>, Vec<_>) = parsed.iter().zip(pick(&flattened, &parsed).iter())
+            .map(|(parsed, flattened)| field_arm(&parsed.path, flattened))
+            .unzip();
+        let (cases_len, cases) = cases(&names_string, &flattened);
+
+        // flattened fields have no path of their own to reset them with
+        let resettable: Vec<_> = fields.iter().zip(names_string.iter()).zip(0u32..)
+            .filter(|((field, _), _)| !field.flatten)
+            .collect();
+        let reset_method_names: Vec<_> = resettable.iter().map(|((field, _), _)| {
+            format_ident!("reset_{}", field_name(&field.ident, field.index))
+        }).collect();
+        let indices: Vec<_> = resettable.iter().map(|(_, index)| *index).collect();
+        let (reset_names_string, reset_indices): (Vec<_>, Vec<_>) = parsed.iter()
+            .filter(|parsed| !fields[parsed.index].flatten)
+            .map(|parsed| (format!("{} reset", parsed.path), parsed.index as u32))
+            .unzip();
+        let display_resets: Vec<_> = fields.iter().zip(names_string.iter()).map(|(field, name)| {
+            if field.flatten {
+                return quote!({});
+            }
+            let name = format!("{} reset", name);
+            quote!(write!(f, "{}", #name)?)
+        }).collect();
+        let display_reset_field_arm = reset_field_arm(quote!(#setter_enum_ident::__ResetField), &display_resets, quote!({}));
+        // the getter of a field resets it as a whole if it is the empty path, and is converted
+        // into the field's reset setter otherwise, see `Getter::reset_setter`
+        let decl_indices: Vec<_> = fields.iter().map(|field| field.index).collect();
+        let reset_ins: Vec<_> = fields.iter().zip(names.iter()).zip(0u32..).map(|((field, name), index)| {
+            let nested = quote!(Some(#setter_enum_ident::#name(inner.reset_setter()?)));
+            if field.flatten {
+                return nested;
+            }
+            quote! {
+                if inner.is_empty_path() {
+                    Some(#setter_enum_ident::__ResetField(#index))
+                } else {
+                    #nested
+                }
+            }
+        }).collect();
+
+        let (id_names, ids) = with_ids(names.iter().zip(fields.iter().map(|field| field.id)));
+
+        tokens.extend(impl_copy(setter_enum_ident, &generics, &setter_types));
+        tokens.extend(impl_default(setter_enum_ident, &generics));
 
         tokens.extend(quote! {
             #[automatically_derived]
-            #[derive(Default, Copy, Clone)]
             #[derive(#(#inner_derives),*)]
             #[allow(non_camel_case_types)]
-            #vis enum #setter_enum_ident {
+            #vis enum #setter_enum_ident #impl_generics #where_clause {
                 #(#names(<#types as RemoteSet>::SetterType),)*
-                #[default]
+                __Reset,
+                /// index of the field among the writable fields
+                __ResetField(u32),
                 __None,
             }
 
             #[allow(non_snake_case)]
-            impl #impl_generics #setter_enum_ident {
+            impl #impl_generics #setter_enum_ident #ty_generics #where_clause {
                 #(#vis fn #method_names<F>(&self, func: F) -> Self where F: Fn(<#types as RemoteSet>::SetterType) -> <#types as RemoteSet>::SetterType {
                     #setter_enum_ident::#names(func(<#types as RemoteSet>::SetterType::default()))
                 })*
+
+                #vis fn reset(&self) -> Self {
+                    #setter_enum_ident::__Reset
+                }
+
+                const SETTER_CASES: [&'static str; #cases_len] = #cases;
+
+                #(#vis fn #reset_method_names(&self) -> Self {
+                    #setter_enum_ident::__ResetField(#indices)
+                })*
             }
 
-            impl Setter for #setter_enum_ident {
-                fn parse_setter<T: Sized>(&self, x: &str, set: T) -> Option<Self> {
+            impl #impl_generics Setter for #setter_enum_ident #ty_generics #where_clause {
+                const FIELDS: &'static [&'static str] = &Self::SETTER_CASES;
+
+                fn parse_setter<__T: 'static>(&self, x: &str, set: __T) -> Option<Self> {
                     match &x[..] {
-                        #(s if s.starts_with(#names_string) => {
-                            return Some(#setter_enum_ident::#names(<#types as RemoteSet>::SetterType::default().parse_setter(&s[#names_string.len()..], set)?));
-                        })*,
+                        " reset" => {
+                            if core::any::TypeId::of::<__T>() != core::any::TypeId::of::<()>() {
+                                return None;
+                            }
+                            return Some(#setter_enum_ident::__Reset);
+                        },
+                        #(#reset_names_string => {
+                            if core::any::TypeId::of::<__T>() != core::any::TypeId::of::<()>() {
+                                return None;
+                            }
+                            return Some(#setter_enum_ident::__ResetField(#reset_indices));
+                        },)*
+                        #(s if #guards => {
+                            return Some(#setter_enum_ident::#parsed_names(<#parsed_types as RemoteSet>::SetterType::default().parse_setter(#rests, set)?));
+                        },)*
                         _ => {
                             return None;
                         }
@@ -105,43 +483,70 @@ impl Receiver {
 
                 fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
                     match &x[..] {
-                        #(s if s.starts_with(#names_string) => {
-                            return Some(#setter_enum_ident::#names(<#types as RemoteSet>::SetterType::default().parse_setter_numeric(&s[#names_string.len()..], set)?));
-                        })*,
+                        #(s if #guards => {
+                            return Some(#setter_enum_ident::#parsed_names(<#parsed_types as RemoteSet>::SetterType::default().parse_setter_numeric(#rests, set)?));
+                        },)*
                         _ => {
                             return None;
                         }
                     };
                 }
+
+                fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+                    match self {
+                        #(#setter_enum_ident::#id_names(x) => {
+                            let (id, ids) = ids.split_first_mut()?;
+                            *id = #ids;
+                            Some(x.to_ids(ids)? + 1)
+                        },)*
+                        _ => None,
+                    }
+                }
+
+                fn parse_setter_ids<__T: 'static>(&self, ids: &[u32], set: __T) -> Option<Self> {
+                    match ids {
+                        #([#ids, ids @ ..] => Some(#setter_enum_ident::#id_names(Setter::parse_setter_ids(&Default::default(), ids, set)?)),)*
+                        _ => None,
+                    }
+                }
+
+                fn parse_setter_ids_numeric(&self, ids: &[u32], set: f64) -> Option<Self> {
+                    match ids {
+                        #([#ids, ids @ ..] => Some(#setter_enum_ident::#id_names(Setter::parse_setter_ids_numeric(&Default::default(), ids, set)?)),)*
+                        _ => None,
+                    }
+                }
+
+                fn reset_whole() -> Option<Self> {
+                    Some(#setter_enum_ident::__Reset)
+                }
+
+                #[allow(unused_variables)]
+                fn reset_in<__G: Getter>(segment: ResetSegment, inner: &__G) -> Option<Self> {
+                    match segment {
+                        #(ResetSegment::Field(#decl_indices) => #reset_ins,)*
+                        _ => None,
+                    }
+                }
             }
 
-            impl core::fmt::Display for #setter_enum_ident {
+            impl #impl_generics core::fmt::Display for #setter_enum_ident #ty_generics #where_clause {
                 fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     match self {
                         #(#setter_enum_ident::#names(x) => {
-                            write!(f, #names_string)?;
+                            write!(f, "{}", #names_string)?;
                             write!(f, "{}", x)?;
                         },)*
-                        _ => {
-                            unreachable!();
-                        }
+                        #setter_enum_ident::__Reset => write!(f, " reset")?,
+                        #display_reset_field_arm,
+                        #setter_enum_ident::__None => {}
                     }
                     Ok(())
                 }
             }
+        });
 
-            #[allow(non_snake_case)]
-            impl #impl_generics RemoteSet for #ident #ty_generics #where_clause {
-                type SetterType = #setter_enum_ident;
-
-                fn set(&mut self, x: Self::SetterType) -> Result<(), ()> {
-                    match x {
-                        #(#setter_enum_ident::#names(x) => self.#names.set(x),)*
-                        #setter_enum_ident::__None => { unimplemented!() }
-                    }
-                }
-            }
-        })
+        generics
     }
 }
 
@@ -151,15 +556,88 @@ impl Receiver {
 struct ReceiverField {
     ident: Option<Ident>,
     ty: Type,
+    #[darling(skip)]
+    index: usize,
     #[darling(default)]
     skip: bool,
     #[darling(default)]
     write_only: bool,
     #[darling(default)]
     read_only: bool,
+    #[darling(default)]
+    rename: Option<String>,
+    #[darling(default)]
+    range: Option<Range>,
+    /// `fn(&Container, &<T as RemoteSet>::SetterType) -> Result<(), Error>` called before writing
+    #[darling(default)]
+    validate: Option<Path>,
+    /// `fn(&mut Container)` called after the field was written
+    #[darling(default)]
+    on_set: Option<Path>,
+    /// stable id of the field in numeric paths, see `Setter::to_ids`
+    #[darling(default)]
+    id: Option<u32>,
+    #[darling(default)]
+    unit: Option<String>,
+    #[darling(default)]
+    description: Option<String>,
+    /// value the field is reset to, instead of resetting it recursively
+    #[darling(default)]
+    default: Option<AttrExpr>,
+    /// splices the fields of the field's type into the paths of the container
+    #[darling(default)]
+    flatten: bool,
+    /// other paths the field is parsed from, e.g. its path before it was renamed
+    #[darling(default, multiple)]
+    alias: Vec<String>,
+    #[darling(default)]
+    deprecated: bool,
+    /// reads and writes the field as a whole through `Opaque<T>`
+    #[darling(default)]
+    opaque: bool,
+    /// mirror of the foreign type of the field, writing it through `ProxyMut`
+    #[darling(default)]
+    with: Option<Type>,
+}
+
+impl ReceiverField {
+    fn proxy(&self) -> Option<Type> {
+        proxy_type(&self.ty, self.opaque, &self.with)
+    }
+
+    fn access_type(&self) -> Type {
+        access_type(&self.ty, &self.proxy())
+    }
 }
 
 impl Receiver {
+    fn variant(&self, ident: &Ident) -> &ReceiverVariant {
+        self.data
+            .as_ref()
+            .take_enum()
+            .expect("VariantNames only takes enums")
+            .into_iter()
+            .find(|v| &v.ident == ident)
+            .unwrap()
+    }
+
+    /// Index of a variant in the declaration of the enum, see `ResetSegment::Field`.
+    fn variant_index(&self, ident: &Ident) -> usize {
+        self.data
+            .as_ref()
+            .take_enum()
+            .expect("VariantNames only takes enums")
+            .iter()
+            .position(|v| &v.ident == ident)
+            .unwrap()
+    }
+
+    /// Path of a variant, e.g. `::A`, see `path_name`.
+    fn variant_path(&self, ident: &Ident) -> String {
+        let variant = self.variant(ident);
+        format!("::{}", path_name(&Some(variant.ident.clone()), 0, &variant.rename, self.rename_all))
+    }
+
     fn unit_variants(&self) -> Vec<Ident> {
         self.data
             .as_ref()
@@ -188,92 +666,442 @@ impl Receiver {
             .take_enum()
             .expect("VariantNames only takes enums")
             .into_iter()
-            .filter(|v| v.fields.is_newtype())
-            .map(|v| strip_ref(v.fields.fields.first().unwrap().ty.clone()))
+            .filter(|v| v.fields.is_newtype() && !v.skip && !v.read_only)
+            .map(|v| v.fields.fields[0].access_type())
             .collect()
     }
 
-    fn other_varient_names(&self) -> Vec<Ident> {
+    /// struct-like variants and tuple variants with more than one field
+    fn struct_variants(&self) -> Vec<ReceiverVariant> {
         self.data
             .as_ref()
             .take_enum()
             .expect("VariantNames only takes enums")
             .into_iter()
-            .filter(|v| !v.fields.is_unit() && !v.fields.is_newtype())
-            .map(|v| v.ident.clone())
+            .filter(|v| !v.fields.is_unit() && !v.fields.is_newtype() && !v.skip && !v.read_only)
+            .cloned()
             .collect()
     }
 }
 
 impl Receiver {
     fn to_tokens_enum(&self, tokens: &mut TokenStream) {
-        let other_varient_names = self.other_varient_names();
-        if other_varient_names.len() > 0 {
-            panic!("VariantNames only supports enums with no unit or newtype variants, {:?}", other_varient_names);
-        }
-
         let ident = &self.ident;
         let setter_enum_ident = format_ident!("{}Setter", ident);
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-
         let unit_variants = self.unit_variants();
         let unit_variant_method_names: Vec<_> = unit_variants.iter().map(|field| {
             format_ident!("make_{}", field)
         }).collect();
 
+        let unit_variants_names_string: Vec<String> = unit_variants.iter().map(|field| {
+            self.variant_path(field)
+        }).collect();
+
         let newtype_variants = self.newtype_variants();
-        let newtype_variant_method_names: Vec<_> = newtype_variants.iter().map(|field| {
+        let newtype_types = self.newtype_types();
+        let struct_variants = self.struct_variants();
+
+        // all types reachable through the enum, which decide the generics of the generated enum
+        let mut types = newtype_types.clone();
+        for variant in struct_variants.iter() {
+            types.extend(fields_to_emit(&variant.fields).iter().map(ReceiverField::access_type));
+        }
+        let mut generics = container_generics(&self.generics, &types, quote!(RemoteSet));
+        // replacing the whole enum needs all of its parameters
+        let mut enum_generics = enum_generics(&self.generics, &types, quote!(RemoteSet), self.replace);
+        if self.replace && !enum_generics.params.is_empty() {
+            let (_, ty_generics, _) = self.generics.split_for_impl();
+            let copy: syn::WherePredicate = syn::parse_quote!(#ident #ty_generics: Copy);
+            generics.make_where_clause().predicates.push(copy.clone());
+            enum_generics.make_where_clause().predicates.push(copy);
+        }
+        // enums deriving `Default` are reset to their `#[default]` variant as a whole
+        let resets_to_default = self.data.as_ref().take_enum().expect("VariantNames only takes enums")
+            .iter()
+            .any(|v| v.is_default());
+        if resets_to_default {
+            let (_, ty_generics, _) = self.generics.split_for_impl();
+            generics.make_where_clause().predicates.push(syn::parse_quote!(#ident #ty_generics: Default));
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+
+        // newtype and struct variants can only be written into while the enum is in that variant
+        let mut data_variants = Vec::new();
+        let mut data_setter_types = Vec::new();
+        let mut set_arms = Vec::new();
+        let mut hooks = Hooks::default();
+
+        for (variant, ty) in newtype_variants.iter().zip(newtype_types.iter()) {
+            let field = &self.variant(variant).fields.fields[0];
+            let set = set_field(field, quote!(inner));
+            // the field is written by the setters of the variant, and reset with the enum
+            let reset = quote!(matches!(x, #setter_enum_ident::__Reset) && matches!(self, #ident::#variant(..)));
+            hooks.add_field(field, |x| quote!(#setter_enum_ident::#variant(#x)), reset, false);
+            data_variants.push(variant.clone());
+            data_setter_types.push(quote!(<#ty as RemoteSet>::SetterType));
+            set_arms.push(quote! {
+                #setter_enum_ident::#variant(x) => match self {
+                    #ident::#variant(inner) => #set,
+                    _ => Err(Error::WrongVariant { depth: 0 })
+                }
+            });
+        }
+
+        for variant in struct_variants.iter() {
+            let variant_ident = &variant.ident;
+            let inner_setter_ident = format_ident!("{}{}Setter", ident, variant_ident);
+
+            let fields = fields_to_emit(&variant.fields);
+            let inner_generics = self.to_tokens_fields(tokens, &inner_setter_ident, &fields, None);
+            let (_, inner_ty_generics, _) = inner_generics.split_for_impl();
+
+            let names: Vec<_> = fields.iter().map(|field|
+                field_variant(&field.ident, field.index)
+            ).collect();
+            let members: Vec<_> = fields.iter().map(|field|
+                field_member(&field.ident, field.index)
+            ).collect();
+            let bindings: Vec<_> = fields.iter().map(|field|
+                format_ident!("__field_{}", field_name(&field.ident, field.index))
+            ).collect();
+            let set_fields: Vec<_> = fields.iter().zip(bindings.iter()).map(|(field, binding)|
+                set_field(field, quote!(#binding))
+            ).collect();
+            let resets: Vec<_> = fields.iter().zip(bindings.iter()).map(|(field, binding)|
+                reset_field(field, quote!(#binding))
+            ).collect();
+            let reset_arms = reset_arms(&inner_setter_ident, &resets);
+            // resetting the whole enum resets the fields of the variant it is in
+            for ((field, name), index) in fields.iter().zip(names.iter()).zip(0u32..) {
+                let reset = quote! {
+                    matches!(x, #setter_enum_ident::#variant_ident(#inner_setter_ident::__Reset | #inner_setter_ident::__ResetField(#index)))
+                        || (matches!(x, #setter_enum_ident::__Reset) && matches!(self, #ident::#variant_ident { .. }))
+                };
+                hooks.add_field(field, |x| quote!(#setter_enum_ident::#variant_ident(#inner_setter_ident::#name(#x))), reset, true);
+            }
+
+            data_variants.push(variant_ident.clone());
+            data_setter_types.push(quote!(#inner_setter_ident #inner_ty_generics));
+            set_arms.push(quote! {
+                #setter_enum_ident::#variant_ident(setter) => match self {
+                    #ident::#variant_ident { #(#members: #bindings,)* .. } => {
+                        let result = match setter {
+                            #(#inner_setter_ident::#names(x) => #set_fields,)*
+                            #reset_arms
+                            #inner_setter_ident::__None => Err(Error::EmptyPath { depth: 0 })
+                        };
+                        result.map_err(Error::nested)
+                    },
+                    _ => Err(Error::WrongVariant { depth: 0 })
+                }
+            });
+        }
+
+        let data_method_names: Vec<_> = data_variants.iter().map(|field| {
             format_ident!("make_{}", field)
         }).collect();
 
-        let newtype_variants_names_string: Vec<String> = newtype_variants.clone().iter().map(|field| {
-            format!("::{}", field)
+        let data_names_string: Vec<String> = data_variants.iter().map(|field| {
+            self.variant_path(field)
         }).collect();
+        let data_variant_indices: Vec<_> = data_variants.iter().map(|variant| self.variant_index(variant)).collect();
+
+        // aliases are parsed like the canonical paths, but not displayed
+        let data_aliases: Vec<&[String]> = data_variants.iter().map(|variant| &self.variant(variant).alias[..]).collect();
+        let parsed_data = parsed_paths(&data_names_string, &data_aliases, "::");
+        let unit_aliases: Vec<&[String]> = unit_variants.iter().map(|variant| &self.variant(variant).alias[..]).collect();
+        let parsed_unit = parsed_paths(&unit_variants_names_string, &unit_aliases, "::");
+        tokens.extend(check_duplicates(&[paths(&parsed_data), paths(&parsed_unit)].concat()));
+        let parsed_data_names_string = paths(&parsed_data);
+        let parsed_data_variants = pick(&data_variants, &parsed_data);
+        let parsed_data_setter_types = pick(&data_setter_types, &parsed_data);
+        let parsed_unit_names_string = paths(&parsed_unit);
+        let parsed_unit_variants = pick(&unit_variants, &parsed_unit);
 
-        let unit_variants_names_string: Vec<String> = unit_variants.clone().iter().map(|field| {
-            format!("::{}", field)
+        let all_variants = self.data.as_ref().take_enum().expect("VariantNames only takes enums");
+        tokens.extend(check_duplicate_ids(all_variants.iter().map(|v| v.id)));
+        for variant in all_variants.iter() {
+            tokens.extend(check_duplicate_ids(variant.fields.iter().map(|field| field.id)));
+        }
+        let (id_variants, ids) = with_ids(data_variants.iter().map(|variant| (variant, self.variant(variant).id)));
+        let (id_unit_variants, unit_ids) = with_ids(unit_variants.iter().map(|variant| (variant, self.variant(variant).id)));
+        let id_unit_variants_names_string: Vec<String> = id_unit_variants.iter().map(|variant| {
+            self.variant_path(variant)
         }).collect();
 
-        let newtype_types = self.newtype_types();
+        // resetting and diffing look into the fields of the active variant, unless the enum is
+        // reset to its `#[default]` variant
+        let mut reset_variant_arms = Vec::new();
+        let mut diff_arms = Vec::new();
+        let mut diff_default_arms = Vec::new();
+        for variant in all_variants.iter() {
+            let variant_ident = &variant.ident;
+            let path = self.variant_path(variant_ident);
+            let fields = fields_to_emit(&variant.fields);
+            if variant.skip || variant.read_only || fields.is_empty() {
+                reset_variant_arms.push(quote!(#ident::#variant_ident { .. } => Ok(())));
+                diff_arms.push(quote!((#ident::#variant_ident { .. }, #ident::#variant_ident { .. }) => {}));
+                diff_default_arms.push(quote!(#ident::#variant_ident { .. } => {}));
+            } else if variant.fields.is_newtype() {
+                let field = &variant.fields.fields[0];
+                let (ty, proxy) = (field.access_type(), field.proxy());
+                let reset = write(&field.ty, &proxy, quote!(inner), |inner| quote!(<#ty as RemoteSet>::reset_to_default(#inner)));
+                let (inner, other) = (read(&field.ty, &proxy, quote!(inner), true), read(&field.ty, &proxy, quote!(other), true));
+                reset_variant_arms.push(quote!(#ident::#variant_ident(inner) => #reset.map_err(Error::nested)));
+                diff_arms.push(quote! {
+                    (#ident::#variant_ident(inner), #ident::#variant_ident(other)) => <#ty as RemoteSet>::diff(#inner, #other, &path.field(#path), changed)
+                });
+                diff_default_arms.push(quote! {
+                    #ident::#variant_ident(inner) => <#ty as RemoteSet>::diff_default(#inner, &path.field(#path), changed)
+                });
+            } else {
+                let members: Vec<_> = fields.iter().map(|field|
+                    field_member(&field.ident, field.index)
+                ).collect();
+                let bindings: Vec<_> = fields.iter().map(|field|
+                    format_ident!("__field_{}", field_name(&field.ident, field.index))
+                ).collect();
+                let other_bindings: Vec<_> = fields.iter().map(|field|
+                    format_ident!("__other_{}", field_name(&field.ident, field.index))
+                ).collect();
+                let resets: Vec<_> = fields.iter().zip(bindings.iter()).map(|(field, binding)|
+                    reset_field(field, quote!(#binding))
+                ).collect();
+                let field_paths: Vec<_> = fields.iter().map(|field| field_path(field, None)).collect();
+                let diffs: Vec<_> = fields.iter().zip(field_paths.iter()).zip(bindings.iter().zip(other_bindings.iter()))
+                    .map(|((field, field_path), (binding, other))| diff_field(field, field_path, quote!(#binding), Some(quote!(#other))))
+                    .collect();
+                let diff_defaults: Vec<_> = fields.iter().zip(field_paths.iter()).zip(bindings.iter())
+                    .map(|((field, field_path), binding)| diff_field(field, field_path, quote!(#binding), None))
+                    .collect();
+                let reset_all = reset_all(&resets);
+                reset_variant_arms.push(quote! {
+                    #ident::#variant_ident { #(#members: #bindings,)* .. } => #reset_all.map_err(Error::nested)
+                });
+                diff_arms.push(quote! {
+                    (#ident::#variant_ident { #(#members: #bindings,)* .. }, #ident::#variant_ident { #(#members: #other_bindings,)* .. }) => {
+                        let path = path.field(#path);
+                        #(#diffs)*
+                    }
+                });
+                diff_default_arms.push(quote! {
+                    #ident::#variant_ident { #(#members: #bindings,)* .. } => {
+                        let path = path.field(#path);
+                        #(#diff_defaults)*
+                    }
+                });
+            }
+        }
 
         let vis = &self.vis;
         let inner_derives = &self.derive;
 
+        // `#[remote(replace)]` adds a `__Replace` variant carrying a whole value of the enum
+        let mut replace_variant = quote!();
+        let mut replace_tokens = quote!();
+        let mut replace_parse_arm = quote!();
+        let mut replace_display_arm = quote!();
+        let mut replace_set_arm = quote!();
+        let mut replace_ids_arm = quote!();
+        let mut replace_into_value = quote!();
+        if self.replace {
+            let all_variants: Vec<_> = self.data
+                .as_ref()
+                .take_enum()
+                .expect("VariantNames only takes enums")
+                .into_iter()
+                .map(|v| v.ident.clone())
+                .collect();
+            let all_variants_display: Vec<String> = self.data
+                .as_ref()
+                .take_enum()
+                .expect("VariantNames only takes enums")
+                .into_iter()
+                .map(|v| {
+                    if v.fields.is_unit() {
+                        format!(" = {}", self.variant_path(&v.ident))
+                    } else {
+                        format!(" = {}(..)", self.variant_path(&v.ident))
+                    }
+                })
+                .collect();
+
+            replace_variant = quote!(__Replace(#ident #ty_generics),);
+            replace_tokens = quote! {
+                impl #enum_impl_generics SetterFrom<#ident #ty_generics> for #setter_enum_ident #enum_ty_generics #enum_where_clause {
+                    fn setter_from(x: #ident #ty_generics) -> Self {
+                        #setter_enum_ident::__Replace(x)
+                    }
+                }
+            };
+            replace_parse_arm = quote! {
+                "" => {
+                    let mut set = Some(set);
+                    let set = (&mut set as &mut dyn core::any::Any).downcast_mut::<Option<#ident #ty_generics>>()?.take()?;
+                    return Some(#setter_enum_ident::__Replace(set));
+                },
+            };
+            replace_display_arm = quote! {
+                #setter_enum_ident::__Replace(x) => match x {
+                    #(#ident::#all_variants { .. } => write!(f, "{}", #all_variants_display)?,)*
+                },
+            };
+            replace_ids_arm = quote!(#setter_enum_ident::__Replace(_) => Some(0),);
+            replace_into_value = quote! {
+                fn into_value<__T: 'static>(self) -> Option<__T> {
+                    match self {
+                        #setter_enum_ident::__Replace(x) => {
+                            let mut x = Some(x);
+                            (&mut x as &mut dyn core::any::Any).downcast_mut::<Option<__T>>()?.take()
+                        }
+                        _ => None,
+                    }
+                }
+            };
+            // the whole value is checked against the `#[remote(range(..))]` of its fields, like
+            // writing the fields one by one
+            let mut range_arms = Vec::new();
+            let newtype_variants = newtype_variants.iter().map(|variant| self.variant(variant));
+            for variant in newtype_variants.chain(struct_variants.iter()) {
+                let variant_ident = &variant.ident;
+                let fields: Vec<_> = fields_to_emit(&variant.fields).into_iter().filter(|field| field.range.is_some()).collect();
+                if fields.is_empty() {
+                    continue;
+                }
+                let members: Vec<_> = fields.iter().map(|field| field_member(&field.ident, field.index)).collect();
+                let bindings: Vec<_> = fields.iter().map(|field|
+                    format_ident!("__field_{}", field_name(&field.ident, field.index))
+                ).collect();
+                let checks: Vec<_> = fields.iter().map(|field|
+                    field.range.as_ref().unwrap().check(&strip_ref(field.ty.clone()))
+                ).collect();
+                range_arms.push(quote! {
+                    #ident::#variant_ident { #(#members: #bindings,)* .. } => {
+                        #({
+                            let x = *#bindings;
+                            *#bindings = #checks?;
+                        })*
+                    }
+                });
+            }
+            replace_set_arm = if range_arms.is_empty() {
+                quote! {
+                    #setter_enum_ident::__Replace(x) => {
+                        *self = x;
+                        Ok(())
+                    },
+                }
+            } else {
+                quote! {
+                    #setter_enum_ident::__Replace(mut x) => {
+                        match &mut x {
+                            #(#range_arms)*
+                            _ => {}
+                        }
+                        *self = x;
+                        Ok(())
+                    },
+                }
+            };
+        }
+
+        let reset_arm = if resets_to_default {
+            quote! {
+                #setter_enum_ident::__Reset => {
+                    *self = Default::default();
+                    Ok(())
+                }
+            }
+        } else {
+            quote! {
+                #setter_enum_ident::__Reset => match self {
+                    #(#reset_variant_arms,)*
+                }
+            }
+        };
+        // the `#[default]` variant is a unit variant, so values in it have no fields to differ
+        let diff_default_variant = if resets_to_default {
+            quote! {
+                let default: Self = Default::default();
+                if core::mem::discriminant(self) != core::mem::discriminant(&default) {
+                    return changed(path);
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let body = hooks.wrap(self, quote! {
+            match x {
+                #(#setter_enum_ident::#unit_variants => {
+                    *self = #ident::#unit_variants;
+                    Ok(())
+                },)*
+                #(#set_arms,)*
+                #replace_set_arm
+                #reset_arm,
+                #setter_enum_ident::__None => Err(Error::EmptyPath { depth: 0 })
+            }
+        });
+
+        let mut copy_types = data_setter_types.clone();
+        if self.replace {
+            copy_types.push(quote!(#ident #ty_generics));
+        }
+        tokens.extend(impl_copy(&setter_enum_ident, &enum_generics, &copy_types));
+        tokens.extend(impl_default(&setter_enum_ident, &enum_generics));
+
         tokens.extend(quote! {
             #[automatically_derived]
-            #[derive(Default, Copy, Clone)]
             #[derive(#(#inner_derives),*)]
             #[allow(non_camel_case_types)]
-            #vis enum #setter_enum_ident #ty_generics {
+            #vis enum #setter_enum_ident #enum_impl_generics #enum_where_clause {
                 #(#unit_variants,)*
-                #(#newtype_variants(<#newtype_types as RemoteSet>::SetterType),)*
-                #[default]
+                #(#data_variants(#data_setter_types),)*
+                #replace_variant
+                __Reset,
                 __None,
             }
 
+            #replace_tokens
+
             #[allow(non_snake_case)]
-            impl #impl_generics #setter_enum_ident #ty_generics {
+            impl #enum_impl_generics #setter_enum_ident #enum_ty_generics #enum_where_clause {
                 #(#vis fn #unit_variant_method_names<F>(&self, func: F) -> Self where F: Fn(()) -> () {
                     #setter_enum_ident::#unit_variants
                 })*
 
-                #(#vis fn #newtype_variant_method_names<F>(&self, func: F) -> Self
-                    where F: Fn(<#newtype_types as RemoteSet>::SetterType) -> <#newtype_types as RemoteSet>::SetterType {
-                        #setter_enum_ident::#newtype_variants(func(<#newtype_types as RemoteSet>::SetterType::default()))
+                #(#vis fn #data_method_names<F>(&self, func: F) -> Self
+                    where F: Fn(#data_setter_types) -> #data_setter_types {
+                        #setter_enum_ident::#data_variants(func(<#data_setter_types>::default()))
                 })*
+
+                #vis fn reset(&self) -> Self {
+                    #setter_enum_ident::__Reset
+                }
             }
 
-            impl Setter for #setter_enum_ident {
-                fn parse_setter<T: Sized>(&self, x: &str, set: T) -> Option<Self> {
+            impl #enum_impl_generics Setter for #setter_enum_ident #enum_ty_generics #enum_where_clause {
+                fn parse_setter<__T: 'static>(&self, x: &str, set: __T) -> Option<Self> {
                     match &x[..] {
-                        #(s if s.starts_with(#newtype_variants_names_string) => {
-                            return Some(#setter_enum_ident::#newtype_variants(<#newtype_types as RemoteSet>::SetterType::default().parse_setter(&s[#newtype_variants_names_string.len()..], set)?));
+                        " reset" => {
+                            if core::any::TypeId::of::<__T>() != core::any::TypeId::of::<()>() {
+                                return None;
+                            }
+                            return Some(#setter_enum_ident::__Reset);
+                        },
+                        #(s if FieldsType::starts_with_segment(s, #parsed_data_names_string) => {
+                            return Some(#setter_enum_ident::#parsed_data_variants(<#parsed_data_setter_types>::default().parse_setter(&s[#parsed_data_names_string.len()..], set)?));
                         },)*
-                        #(#unit_variants_names_string => {
-                            assert_eq!(core::mem::size_of::<T>(), 0);
-                            return Some(#setter_enum_ident::#unit_variants);
+                        #(#parsed_unit_names_string => {
+                            if core::any::TypeId::of::<__T>() != core::any::TypeId::of::<()>() {
+                                return None;
+                            }
+                            return Some(#setter_enum_ident::#parsed_unit_variants);
                         },)*
+                        #replace_parse_arm
                         _ => {
                             return None;
                         }
@@ -282,30 +1110,78 @@ impl Receiver {
 
                 fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
                     match &x[..] {
-                        #(s if s.starts_with(#newtype_variants_names_string) => {
-                            return Some(#setter_enum_ident::#newtype_variants(<#newtype_types as RemoteSet>::SetterType::default().parse_setter_numeric(&s[#newtype_variants_names_string.len()..], set)?));
+                        #(s if FieldsType::starts_with_segment(s, #parsed_data_names_string) => {
+                            return Some(#setter_enum_ident::#parsed_data_variants(<#parsed_data_setter_types>::default().parse_setter_numeric(&s[#parsed_data_names_string.len()..], set)?));
                         },)*
                         _ => {
                             return None;
                         }
                     };
                 }
+
+                #replace_into_value
+
+                // unit variants are written with their id as the last id of the path, replacing
+                // the whole enum with the empty path
+                fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+                    match self {
+                        #(#setter_enum_ident::#id_variants(x) => {
+                            let (id, ids) = ids.split_first_mut()?;
+                            *id = #ids;
+                            Some(x.to_ids(ids)? + 1)
+                        },)*
+                        #(#setter_enum_ident::#id_unit_variants => {
+                            *ids.first_mut()? = #unit_ids;
+                            Some(1)
+                        },)*
+                        #replace_ids_arm
+                        _ => None,
+                    }
+                }
+
+                fn parse_setter_ids<__T: 'static>(&self, ids: &[u32], set: __T) -> Option<Self> {
+                    match ids {
+                        [] => self.parse_setter("", set),
+                        #([#unit_ids] => self.parse_setter(#id_unit_variants_names_string, set),)*
+                        #([#ids, ids @ ..] => Some(#setter_enum_ident::#id_variants(Setter::parse_setter_ids(&Default::default(), ids, set)?)),)*
+                        _ => None,
+                    }
+                }
+
+                fn parse_setter_ids_numeric(&self, ids: &[u32], set: f64) -> Option<Self> {
+                    match ids {
+                        #([#ids, ids @ ..] => Some(#setter_enum_ident::#id_variants(Setter::parse_setter_ids_numeric(&Default::default(), ids, set)?)),)*
+                        _ => None,
+                    }
+                }
+
+                fn reset_whole() -> Option<Self> {
+                    Some(#setter_enum_ident::__Reset)
+                }
+
+                #[allow(unused_variables)]
+                fn reset_in<__G: Getter>(segment: ResetSegment, inner: &__G) -> Option<Self> {
+                    match segment {
+                        #(ResetSegment::Field(#data_variant_indices) => Some(#setter_enum_ident::#data_variants(inner.reset_setter()?)),)*
+                        _ => None,
+                    }
+                }
             }
 
-            impl core::fmt::Display for #setter_enum_ident {
+            impl #enum_impl_generics core::fmt::Display for #setter_enum_ident #enum_ty_generics #enum_where_clause {
                 fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     match self {
-                        #(#setter_enum_ident::#newtype_variants(ref x) => {
-                            write!(f, #newtype_variants_names_string)?;
+                        #(#setter_enum_ident::#data_variants(ref x) => {
+                            write!(f, "{}", #data_names_string)?;
                             write!(f, "{}", x)?;
                         },)*
                         #(#setter_enum_ident::#unit_variants => {
                             write!(f, " = ")?;
-                            write!(f, #unit_variants_names_string)?;
+                            write!(f, "{}", #unit_variants_names_string)?;
                         },)*
-                        _ => {
-                            unreachable!();
-                        }
+                        #replace_display_arm
+                        #setter_enum_ident::__Reset => write!(f, " reset")?,
+                        #setter_enum_ident::__None => {}
                     }
                     Ok(())
                 }
@@ -313,51 +1189,69 @@ impl Receiver {
 
             #[allow(non_snake_case)]
             impl #impl_generics RemoteSet for #ident #ty_generics #where_clause {
-                type SetterType = #setter_enum_ident #ty_generics;
-
-                fn set(&mut self, x: Self::SetterType)  -> Result<(), ()>{
-                    match x {
-                        #(#setter_enum_ident::#unit_variants =>
-                            {
-                                *self = #ident::#unit_variants;
-                                return Ok(())
-                            }
-                        )*
-                        #(#setter_enum_ident::#newtype_variants(setter) =>
-                            match self {
-                                #ident::#newtype_variants(ref mut inner) => {
-                                    return inner.set(setter)
-                                },
-                                _ => {
-                                    return Err(())
-                                }
-                            }
-                        )*
-                        #setter_enum_ident::__None => { unimplemented!() }
-                    };
+                type SetterType = #setter_enum_ident #enum_ty_generics;
+
+                fn set(&mut self, x: Self::SetterType)  -> Result<(), Error>{
+                    #body
+                }
+
+                // through `set`, to call the hooks
+                fn reset_to_default(&mut self) -> Result<(), Error> {
+                    RemoteSet::set(self, #setter_enum_ident::__Reset)
+                }
+
+                // values in different variants differ as a whole
+                #[allow(unreachable_patterns)]
+                fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+                    match (self, other) {
+                        #(#diff_arms,)*
+                        _ => changed(path),
+                    }
+                }
+
+                fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+                    #diff_default_variant
+                    match self {
+                        #(#diff_default_arms,)*
+                    }
                 }
             }
         })
     }
 }
 
-#[derive(FromField, Clone)]
-#[darling(attributes(remote))]
-struct ReceiverFieldVar {
-    ty: Type
-}
-
-
 #[derive(FromVariant, Clone)]
-#[darling(attributes(remote))]
+#[darling(attributes(remote), forward_attrs(default))]
 #[allow(dead_code)]
 struct ReceiverVariant {
     ident: Ident,
-    fields: Fields<ReceiverFieldVar>,
+    fields: Fields<ReceiverField>,
+    /// `#[default]` of `#[derive(Default)]`
+    attrs: Vec<Attribute>,
     #[darling(default)]
     skip: bool,
     #[darling(default)]
     write_only: bool,
     #[darling(default)]
     read_only: bool,
+    #[darling(default)]
+    rename: Option<String>,
+    #[darling(default)]
+    id: Option<u32>,
+    #[darling(default)]
+    unit: Option<String>,
+    #[darling(default)]
+    description: Option<String>,
+    /// other paths the variant is parsed from, without the leading `::`
+    #[darling(default, multiple)]
+    alias: Vec<String>,
+    #[darling(default)]
+    deprecated: bool,
+}
+
+impl ReceiverVariant {
+    /// Whether this is the `#[default]` variant of `#[derive(Default)]`.
+    fn is_default(&self) -> bool {
+        self.attrs.iter().any(|attr| attr.path.is_ident("default"))
+    }
 }
@@ -0,0 +1,153 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::{vec, Error, FieldMetadata, FieldPath, FieldsType, NullGetter, RemoteGet, RemoteSet, SetterFrom, StringBuf, VecGetter, VecSetter, VecValue};
+use crate::vec::UNBOUNDED;
+
+/// Maximum length in bytes of a `String` that can be read or written remotely, as its setter and
+/// value are copied into a `StringBuf<STRING_LEN>`. Use a `heapless::String<N>` for longer strings.
+pub const STRING_LEN: usize = 64;
+
+/// `Push` grows the `Vec` like `Vec::push`, use a `heapless::Vec<T, N>` to bound its length.
+impl<T: RemoteSet + Default> RemoteSet for Vec<T> {
+    type SetterType = VecSetter<T::SetterType, UNBOUNDED>;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        match x {
+            VecSetter::Index(idx, s) => match self.get_mut(idx) {
+                Some(v) => v.set(s).map_err(Error::nested),
+                None => Err(Error::IndexOutOfBounds { depth: 0 }),
+            },
+            VecSetter::Push(s) => {
+                let mut v = T::default();
+                v.set(s).map_err(Error::nested)?;
+                self.push(v);
+                Ok(())
+            }
+            VecSetter::Pop => self.pop().map(|_| ()).ok_or(Error::IndexOutOfBounds { depth: 0 }),
+            VecSetter::Truncate(len) => {
+                self.truncate(vec::truncate_len(len)?);
+                Ok(())
+            }
+            VecSetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn reset_to_default(&mut self) -> Result<(), Error> {
+        self.clear();
+        Ok(())
+    }
+
+    fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        vec::diff(self, other, path, changed)
+    }
+
+    fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        if !self.is_empty() {
+            changed(path);
+        }
+    }
+}
+
+impl<T: RemoteGet> RemoteGet for Vec<T> {
+    type ValueType = VecValue<T::ValueType, UNBOUNDED>;
+    type GetterType = VecGetter<T::GetterType, UNBOUNDED>;
+
+    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+        vec::get(self, x)
+    }
+
+    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        vec::hydrate::<T, UNBOUNDED>(x, buf)
+    }
+
+    fn dynamic_fields(&self, x: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+        vec::dynamic_fields(self, x, keys)
+    }
+
+    fn get_metadata(x: &str) -> Option<FieldMetadata> {
+        vec::get_metadata::<T, UNBOUNDED>(x)
+    }
+}
+
+impl<const N: usize> From<StringBuf<N>> for String {
+    fn from(x: StringBuf<N>) -> Self {
+        String::from(x.as_str())
+    }
+}
+
+impl SetterFrom<String> for StringBuf<STRING_LEN> {
+    /// Strings longer than `STRING_LEN` bytes give an overflowed setter, see `StringBuf`.
+    fn setter_from(x: String) -> Self {
+        StringBuf::copy(&x)
+    }
+}
+
+impl RemoteSet for String {
+    type SetterType = StringBuf<STRING_LEN>;
+
+    /// Returns `Error::CapacityExceeded` if the setter was built from a string longer than
+    /// `STRING_LEN` bytes.
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        x.check()?;
+        *self = x.into();
+        Ok(())
+    }
+
+    impl_reset_leaf!();
+}
+
+impl RemoteGet for String {
+    type ValueType = StringBuf<STRING_LEN>;
+    type GetterType = NullGetter;
+
+    /// Returns `Error::CapacityExceeded` if the string is longer than `STRING_LEN` bytes.
+    fn get(&self, _: Self::GetterType) -> Result<Self::ValueType, Error> {
+        StringBuf::new(self).ok_or(Error::CapacityExceeded { depth: 0 })
+    }
+
+    fn hydrate(_: NullGetter, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        StringBuf::hydrate(buf)
+    }
+}
+
+impl<T: RemoteSet> RemoteSet for Box<T> {
+    type SetterType = T::SetterType;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        (**self).set(x)
+    }
+
+    fn reset_to_default(&mut self) -> Result<(), Error> {
+        (**self).reset_to_default()
+    }
+
+    fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        (**self).diff(other, path, changed)
+    }
+
+    fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        (**self).diff_default(path, changed)
+    }
+}
+
+impl<T: RemoteGet> RemoteGet for Box<T> {
+    type ValueType = T::ValueType;
+    type GetterType = T::GetterType;
+
+    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+        (**self).get(x)
+    }
+
+    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        T::hydrate(x, buf)
+    }
+
+    fn dynamic_fields(&self, x: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+        (**self).dynamic_fields(x, keys)
+    }
+
+    fn get_metadata(x: &str) -> Option<FieldMetadata> {
+        T::get_metadata(x)
+    }
+}
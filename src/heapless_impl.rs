@@ -0,0 +1,109 @@
+use heapless::{String, Vec};
+use crate::{vec, Error, FieldMetadata, FieldPath, FieldsType, NullGetter, RemoteGet, RemoteSet, SetterFrom, StringBuf, VecGetter, VecSetter, VecValue};
+
+impl<T: RemoteSet + Default, const N: usize> RemoteSet for Vec<T, N> {
+    type SetterType = VecSetter<T::SetterType, N>;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        match x {
+            VecSetter::Index(idx, s) => match self.get_mut(idx) {
+                Some(v) => v.set(s).map_err(Error::nested),
+                None => Err(Error::IndexOutOfBounds { depth: 0 }),
+            },
+            VecSetter::Push(s) => {
+                if self.is_full() {
+                    return Err(Error::IndexOutOfBounds { depth: 0 });
+                }
+                let mut v = T::default();
+                v.set(s).map_err(Error::nested)?;
+                self.push(v).map_err(|_| Error::IndexOutOfBounds { depth: 0 })
+            }
+            VecSetter::Pop => self.pop().map(|_| ()).ok_or(Error::IndexOutOfBounds { depth: 0 }),
+            VecSetter::Truncate(len) => {
+                self.truncate(vec::truncate_len(len)?);
+                Ok(())
+            }
+            VecSetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn reset_to_default(&mut self) -> Result<(), Error> {
+        self.clear();
+        Ok(())
+    }
+
+    fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        vec::diff(self, other, path, changed)
+    }
+
+    fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        if !self.is_empty() {
+            changed(path);
+        }
+    }
+}
+
+impl<T: RemoteGet, const N: usize> RemoteGet for Vec<T, N> {
+    type ValueType = VecValue<T::ValueType, N>;
+    type GetterType = VecGetter<T::GetterType, N>;
+
+    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+        vec::get(self, x)
+    }
+
+    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        vec::hydrate::<T, N>(x, buf)
+    }
+
+    fn dynamic_fields(&self, x: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+        vec::dynamic_fields(self, x, keys)
+    }
+
+    fn get_metadata(x: &str) -> Option<FieldMetadata> {
+        vec::get_metadata::<T, N>(x)
+    }
+}
+
+impl<const N: usize> From<StringBuf<N>> for String<N> {
+    fn from(x: StringBuf<N>) -> Self {
+        // cannot fail, both have the same capacity
+        String::try_from(x.as_str()).unwrap()
+    }
+}
+
+impl<const N: usize> From<&String<N>> for StringBuf<N> {
+    fn from(x: &String<N>) -> Self {
+        StringBuf::new(x.as_str()).unwrap()
+    }
+}
+
+impl<const N: usize> SetterFrom<String<N>> for StringBuf<N> {
+    fn setter_from(x: String<N>) -> Self {
+        StringBuf::from(&x)
+    }
+}
+
+impl<const N: usize> RemoteSet for String<N> {
+    type SetterType = StringBuf<N>;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        x.check()?;
+        *self = x.into();
+        Ok(())
+    }
+
+    impl_reset_leaf!();
+}
+
+impl<const N: usize> RemoteGet for String<N> {
+    type ValueType = StringBuf<N>;
+    type GetterType = NullGetter;
+
+    fn get(&self, _: Self::GetterType) -> Result<Self::ValueType, Error> {
+        Ok(StringBuf::from(self))
+    }
+
+    fn hydrate(_: NullGetter, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        StringBuf::hydrate(buf)
+    }
+}
@@ -1,5 +1,9 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::any::Any;
 use core::fmt;
 use core::fmt::{Display, Formatter};
 use core::hash::Hash;
@@ -8,9 +12,53 @@ use core::ops::Index;
 pub use remote_obj_derive::{RemoteSetter, RemoteGetter, setter, getter};
 use bincode::{Encode, Decode};
 
+// `RemoteSet` methods of leaves, which are reset and compared as a whole
+macro_rules! impl_reset_leaf {
+    () => {
+        fn reset_to_default(&mut self) -> Result<(), Error> {
+            *self = Default::default();
+            Ok(())
+        }
+
+        fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+            if self != other {
+                changed(path);
+            }
+        }
+
+        fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+            self.diff(&Default::default(), path, changed);
+        }
+    };
+}
+
+mod option;
+pub use option::{OptionGetter, OptionSetter, OptionValue};
+mod opaque;
+pub use opaque::{Opaque, OpaqueSetter};
+#[cfg(any(feature = "heapless", feature = "alloc"))]
+mod vec;
+#[cfg(any(feature = "heapless", feature = "alloc"))]
+pub use vec::{VecGetter, VecSetter, VecValue};
+#[cfg(any(feature = "heapless", feature = "alloc"))]
+mod string_buf;
+#[cfg(any(feature = "heapless", feature = "alloc"))]
+pub use string_buf::StringBuf;
+#[cfg(feature = "heapless")]
+mod heapless_impl;
+#[cfg(feature = "alloc")]
+mod map;
+#[cfg(feature = "alloc")]
+pub use map::{MapGetter, MapKey, MapSetter, MapValue, KEY_LEN};
+#[cfg(feature = "alloc")]
+mod alloc_impl;
+#[cfg(feature = "alloc")]
+pub use alloc_impl::STRING_LEN;
+
 pub mod prelude {
     pub use crate::{
-        RemoteSetter, RemoteGetter, setter, getter, Setter, Getter, Value, RemoteSet, RemoteGet, NullGetter, FieldsType
+        RemoteSetter, RemoteGetter, setter, getter, Setter, Getter, Value, RemoteSet, RemoteGet, NullGetter, FieldsType,
+        Error, SetterFrom, FieldMetadata, ValueRange, FieldPath, Proxy, ProxyMut, Opaque, ResetSegment
     };
     pub use core::any::Any;
 }
@@ -19,42 +67,402 @@ pub mod prelude {
 pub enum FieldsType {
     Fields(&'static [&'static str]),
     Arr(usize),
+    /// a sequence whose length is only known at runtime, see `RemoteGet::dynamic_fields`
+    Vec,
+    /// a map whose keys are only known at runtime, see `RemoteGet::dynamic_fields`
+    Map,
     Terminal
 }
 
-pub trait Value: Sized + Copy {
+impl FieldsType {
+    /// Used by the derived getters and setters to list the paths of the fields of a struct,
+    /// splicing in the fields of its `#[remote(flatten)]` fields. Fails to compile if a path
+    /// appears twice.
+    #[doc(hidden)]
+    pub const fn concat<const N: usize>(parts: &[&[&'static str]]) -> [&'static str; N] {
+        let mut fields = [""; N];
+        let mut len = 0;
+        let mut i = 0;
+        while i < parts.len() {
+            let mut j = 0;
+            while j < parts[i].len() {
+                let mut k = 0;
+                while k < len {
+                    if str_eq(fields[k], parts[i][j]) {
+                        panic!("duplicate field path in a struct with `#[remote(flatten)]` fields");
+                    }
+                    k += 1;
+                }
+                fields[len] = parts[i][j];
+                len += 1;
+                j += 1;
+            }
+            i += 1;
+        }
+        fields
+    }
+
+    /// Used by the derived getters and setters: whether the path `x` starts with the path `name` of
+    /// a field or variant, followed by the end of the path or the start of the next segment (so
+    /// `.1` doesn't match `.10`, and `.kp` doesn't match `.kpx`).
+    #[doc(hidden)]
+    pub fn starts_with_segment(x: &str, name: &str) -> bool {
+        match x.strip_prefix(name) {
+            Some(rest) => rest.is_empty() || rest == "VARIANT" || rest.starts_with(['.', '[', ':', ' ']),
+            None => false,
+        }
+    }
+
+    /// Whether the path `x` starts with one of `fields` (see `starts_with_segment`), used to find
+    /// the `#[remote(flatten)]` field a path belongs to.
+    #[doc(hidden)]
+    pub fn in_fields(fields: &[&str], x: &str) -> bool {
+        fields.iter().any(|field| Self::starts_with_segment(x, field))
+    }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Static information about the field at a path, see `RemoteGet::get_metadata`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct FieldMetadata {
+    /// type of the field as declared in the derived struct or enum (e.g. `Option<u16>`), the name
+    /// of the struct or enum for the root path and `Enum::Variant` for variants without a single
+    /// field; empty for paths inside types without a derive, such as the elements of a sequence
+    pub type_name: &'static str,
+    /// doc comment of the field or variant
+    pub doc: Option<&'static str>,
+    /// set with `#[remote(unit = "V")]`
+    pub unit: Option<&'static str>,
+    /// set with `#[remote(description = "...")]`
+    pub description: Option<&'static str>,
+    /// bounds set with `#[remote(range(min = .., max = ..))]`
+    pub range: Option<ValueRange>,
+    /// set with `#[remote(deprecated)]`, or if the path uses one of the `#[remote(alias = "...")]`es
+    /// of the field or variant
+    pub deprecated: bool,
+}
+
+/// Bounds of a numeric field, see `FieldMetadata`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ValueRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ValueRange {
+    /// Used by the derived `RemoteSet::set` for fields with `#[remote(range(..))]`, returns `x`,
+    /// the nearest bound if `clamp` is set, or `Error::OutOfRange`.
+    #[doc(hidden)]
+    pub fn check<T: PartialOrd>(x: T, min: T, max: T, clamp: bool) -> Result<T, Error> {
+        if x >= min && x <= max {
+            Ok(x)
+        } else if clamp && x < min {
+            Ok(min)
+        } else if clamp && x > max {
+            Ok(max)
+        } else {
+            Err(Error::OutOfRange { depth: 0 })
+        }
+    }
+}
+
+/// Error returned by `RemoteSet::set`, `RemoteGet::get` and `RemoteGet::hydrate`.
+///
+/// `depth` is the index of the path segment where the error happened, counted from the root object
+/// (e.g. for `.e::B.a` a wrong variant is reported at depth 1).
+#[derive(Encode, Decode, Hash, Eq, Clone, Copy, PartialEq, Debug)]
+pub enum Error {
+    /// the enum is not in the variant named by the path
+    WrongVariant { depth: usize },
+    /// the array index is past the end of the array
+    IndexOutOfBounds { depth: usize },
+    /// the buffer is too short to hydrate the value from
+    BufferTooShort { depth: usize },
+    /// the buffer does not contain a valid encoding of the value
+    InvalidEncoding { depth: usize },
+    /// the getter or setter does not name anything (e.g. the `Default` value of a derived getter)
+    EmptyPath { depth: usize },
+    /// the map does not contain the key
+    KeyNotFound { depth: usize },
+    /// the value does not fit in the fixed capacity buffer used to transfer it
+    CapacityExceeded { depth: usize },
+    /// the value is outside of the bounds set with `#[remote(range(..))]`
+    OutOfRange { depth: usize },
+    /// the path does not name a field or a derived value which can be reset, see `RemoteSet::reset`
+    NotResettable { depth: usize },
+}
+
+impl Error {
+    pub fn depth(&self) -> usize {
+        match *self {
+            Error::WrongVariant { depth } |
+            Error::IndexOutOfBounds { depth } |
+            Error::BufferTooShort { depth } |
+            Error::InvalidEncoding { depth } |
+            Error::EmptyPath { depth } |
+            Error::KeyNotFound { depth } |
+            Error::CapacityExceeded { depth } |
+            Error::OutOfRange { depth } |
+            Error::NotResettable { depth } => depth,
+        }
+    }
+
+    /// Moves the error one path segment further away from the root, used when an error is
+    /// propagated out of a nested field, variant or array element.
+    pub fn nested(self) -> Self {
+        match self {
+            Error::WrongVariant { depth } => Error::WrongVariant { depth: depth + 1 },
+            Error::IndexOutOfBounds { depth } => Error::IndexOutOfBounds { depth: depth + 1 },
+            Error::BufferTooShort { depth } => Error::BufferTooShort { depth: depth + 1 },
+            Error::InvalidEncoding { depth } => Error::InvalidEncoding { depth: depth + 1 },
+            Error::EmptyPath { depth } => Error::EmptyPath { depth: depth + 1 },
+            Error::KeyNotFound { depth } => Error::KeyNotFound { depth: depth + 1 },
+            Error::CapacityExceeded { depth } => Error::CapacityExceeded { depth: depth + 1 },
+            Error::OutOfRange { depth } => Error::OutOfRange { depth: depth + 1 },
+            Error::NotResettable { depth } => Error::NotResettable { depth: depth + 1 },
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::WrongVariant { depth } => write!(f, "wrong enum variant at depth {}", depth),
+            Error::IndexOutOfBounds { depth } => write!(f, "index out of bounds at depth {}", depth),
+            Error::BufferTooShort { depth } => write!(f, "buffer too short at depth {}", depth),
+            Error::InvalidEncoding { depth } => write!(f, "invalid encoding at depth {}", depth),
+            Error::EmptyPath { depth } => write!(f, "empty path at depth {}", depth),
+            Error::KeyNotFound { depth } => write!(f, "key not found at depth {}", depth),
+            Error::CapacityExceeded { depth } => write!(f, "capacity exceeded at depth {}", depth),
+            Error::OutOfRange { depth } => write!(f, "value out of range at depth {}", depth),
+            Error::NotResettable { depth } => write!(f, "path can not be reset at depth {}", depth),
+        }
+    }
+}
+
+/// Path of a field, built up while walking an object in `RemoteSet::diff` and displayed like the
+/// paths of `parse_setter` (e.g. `.gains[1].kp`).
+#[derive(Clone, Copy)]
+pub struct FieldPath<'a> {
+    parent: Option<&'a FieldPath<'a>>,
+    segment: PathSegment<'a>,
+}
+
+#[derive(Clone, Copy)]
+enum PathSegment<'a> {
+    Name(&'a str),
+    Index(usize),
+}
+
+impl FieldPath<'static> {
+    /// The empty path of the object itself.
+    pub fn root() -> Self {
+        FieldPath { parent: None, segment: PathSegment::Name("") }
+    }
+}
+
+impl<'a> FieldPath<'a> {
+    /// Path of the field or variant named `name` (including the leading `.` or `::`) below `self`.
+    pub fn field<'b>(&'b self, name: &'b str) -> FieldPath<'b> {
+        FieldPath { parent: Some(self), segment: PathSegment::Name(name) }
+    }
+
+    /// Path of the element `idx` of the sequence at `self`.
+    pub fn index<'b>(&'b self, idx: usize) -> FieldPath<'b> {
+        FieldPath { parent: Some(self), segment: PathSegment::Index(idx) }
+    }
+}
+
+impl Display for FieldPath<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(parent) = self.parent {
+            write!(f, "{}", parent)?;
+        }
+        match self.segment {
+            PathSegment::Name(name) => write!(f, "{}", name),
+            PathSegment::Index(idx) => write!(f, "[{}]", idx),
+        }
+    }
+}
+
+/// Segment of a getter's path, through which `Getter::reset_setter` builds the setter resetting
+/// the value at the path.
+#[doc(hidden)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResetSegment {
+    /// field or variant, by its index in the declaration (`::Some` of an option is the variant 1)
+    Field(usize),
+    Index(usize),
+    #[cfg(feature = "alloc")]
+    Key(StringBuf<KEY_LEN>),
+}
+
+/// Converts `x` to `U` if `T` and `U` are the same type.
+fn downcast<T: 'static, U: 'static>(x: T) -> Option<U> {
+    let mut x = Some(x);
+    (&mut x as &mut dyn Any).downcast_mut::<Option<U>>()?.take()
+}
+
+pub trait Value: Sized + Copy + 'static {
+    /// Writes the value into `x`, returning the number of bytes written or `None` if `x` is
+    /// shorter than `dehydrated_len`.
     fn dehydrate(&self, x: &mut [u8]) -> Option<usize>;
+    fn dehydrated_len(&self) -> usize;
     fn as_float(&self) -> Option<f32> {
         None
     }
-    fn parse_value<T: Sized>(self, x: &str) -> Option<T> {
+    /// Returns the value at path `x`, or `None` if the path does not match or `T` is not the type of the value.
+    fn parse_value<T: 'static>(self, x: &str) -> Option<T> {
         if x.is_empty() {
-            assert_eq!(size_of::<Self>(), size_of::<T>(), "Value::parse_value: size mismatch");
-            Some(unsafe { core::mem::transmute_copy::<Self, T>(&self) })
+            downcast(self)
         } else {
             None
         }
     }
 }
 
-pub trait Setter: Default + Sized + Copy + Display {
-    fn parse_setter<T: Sized>(&self, x: &str, set: T) -> Option<Self> {
+pub trait Setter: Default + Sized + Copy + Display + 'static {
+    /// Paths of the fields of a derived struct, which `#[remote(flatten)]` splices into the paths
+    /// of its container. Empty for other types.
+    const FIELDS: &'static [&'static str] = &[];
+
+    /// Returns a setter writing `set` to path `x`, or `None` if the path does not match or `T` is not the type of the field.
+    fn parse_setter<T: 'static>(&self, x: &str, set: T) -> Option<Self> {
         if x.is_empty() {
-            assert_eq!(size_of::<Self>(), size_of::<T>(), "Setter::parse_setter: size mismatch");
-            Some(unsafe { core::mem::transmute_copy::<T, Self>(&set) })
+            downcast(set)
         } else {
             None
         }
     }
 
     fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self>;
+
+    /// Returns the whole value the setter writes as a `T`, or `None` if it only writes a part of
+    /// it. Leaf setters are their value, and `#[remote(replace)]` setters carry the enum. Used to
+    /// replace options, which can't build their value from a partial setter.
+    fn into_value<T: 'static>(self) -> Option<T> {
+        downcast(self)
+    }
+
+    /// Writes the numeric path of the setter (see `#[remote(id = ..)]`) into `ids`, returning the
+    /// number of ids written, or `None` if `ids` is too short or a field on the path has no id.
+    fn to_ids(&self, _ids: &mut [u32]) -> Option<usize> {
+        Some(0)
+    }
+
+    /// Like `parse_setter`, with the path given as ids, see `to_ids`.
+    fn parse_setter_ids<T: 'static>(&self, ids: &[u32], set: T) -> Option<Self> {
+        if ids.is_empty() {
+            self.parse_setter("", set)
+        } else {
+            None
+        }
+    }
+
+    /// Like `parse_setter_numeric`, with the path given as ids, see `to_ids`.
+    fn parse_setter_ids_numeric(&self, ids: &[u32], set: f64) -> Option<Self> {
+        if ids.is_empty() {
+            self.parse_setter_numeric("", set)
+        } else {
+            None
+        }
+    }
+
+    /// Used by `RemoteSet::reset`: the setter resetting the whole value, `None` if it can't be reset.
+    #[doc(hidden)]
+    fn reset_whole() -> Option<Self> {
+        None
+    }
+
+    /// Used by `RemoteSet::reset`: the setter resetting the value at `segment` followed by the path
+    /// of `inner`, the getter of the value at `segment` (see `Getter::reset_setter`).
+    #[doc(hidden)]
+    fn reset_in<G: Getter>(_segment: ResetSegment, _inner: &G) -> Option<Self> {
+        None
+    }
+}
+
+/// Conversion from the value on the right hand side of `setter!(.. = expr)` to the setter writing it.
+///
+/// Leaf setters are the values themselves, enums with `#[remote(replace)]` additionally accept the
+/// whole enum.
+pub trait SetterFrom<T>: Setter {
+    fn setter_from(x: T) -> Self;
+}
+
+impl<T: Setter> SetterFrom<T> for T {
+    fn setter_from(x: T) -> Self {
+        x
+    }
+}
+
+/// Type through which fields of type `T` are read, for types which don't implement `RemoteGet`
+/// themselves (see `#[remote(opaque)]` and `#[remote(with = "..")]`). The field is converted to
+/// the proxy for every read, which for mirrors of foreign types clones all of its fields.
+pub trait Proxy<T> {
+    fn from_field(x: &T) -> Self;
+}
+
+/// Like `Proxy`, for writing: the field is converted to the proxy, written into and converted back.
+pub trait ProxyMut<T> {
+    fn from_field(x: &T) -> Self;
+    fn into_field(self) -> T;
 }
 
 pub trait RemoteSet {
     type SetterType: Setter;
-    fn set(&mut self, x: Self::SetterType) -> Result<(), ()>;
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error>;
+
+    /// Resets the value to its default. Derived types reset each of their writable fields to
+    /// their `#[remote(default = ..)]`, or recursively if they have none. Enums with a `#[default]`
+    /// variant are reset to it, other enums reset the fields of the active variant. Leaves use
+    /// `Default`, and options and collections become empty.
+    ///
+    /// Types which don't implement it return `Error::NotResettable`. Derived types still reset
+    /// their other fields, and return the first error.
+    fn reset_to_default(&mut self) -> Result<(), Error> {
+        Err(Error::NotResettable { depth: 0 })
+    }
+
+    /// Passes the paths of the leaves which differ between `self` and `other` to `changed`. Values
+    /// in different enum variants or sequences of different lengths are reported as a whole.
+    ///
+    /// Types which don't implement it report the whole value as changed.
+    fn diff(&self, _other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        changed(path)
+    }
 
-    fn dynamic_setter<T>(x: &str, set: T) -> Option<Self::SetterType>
+    /// Like `diff`, comparing with the value `reset_to_default` would reset to, so
+    /// `obj.diff_default(&FieldPath::root(), &mut |path| ..)` lists the changed leaves.
+    fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        changed(path)
+    }
+
+    /// Resets the value at the path of `x` to its default, with the setter `setter!(Type.path reset)`
+    /// (see `reset_to_default`) built from `x` by `Getter::reset_setter`. Only fields and whole
+    /// derived values can be reset, e.g. not the elements of an array of leaves.
+    fn reset(&mut self, x: <Self as RemoteGet>::GetterType) -> Result<(), Error>
+        where Self: RemoteGet + Sized
+    {
+        let setter = x.reset_setter::<Self::SetterType>().ok_or(Error::NotResettable { depth: 0 })?;
+        self.set(setter)
+    }
+
+    fn dynamic_setter<T: 'static>(x: &str, set: T) -> Option<Self::SetterType>
     {
         Self::SetterType::parse_setter::<T>(&Self::SetterType::default(), x, set)
     }
@@ -62,9 +470,23 @@ pub trait RemoteSet {
     {
         Self::SetterType::parse_setter_numeric(&Self::SetterType::default(), x, set)
     }
+
+    fn id_setter<T: 'static>(ids: &[u32], set: T) -> Option<Self::SetterType>
+    {
+        Self::SetterType::parse_setter_ids::<T>(&Self::SetterType::default(), ids, set)
+    }
+
+    fn id_setter_numeric(ids: &[u32], set: f64) -> Option<Self::SetterType>
+    {
+        Self::SetterType::parse_setter_ids_numeric(&Self::SetterType::default(), ids, set)
+    }
 }
 
 pub trait Getter: Default + Hash + Eq + Clone + Copy + Display {
+    /// Paths of the fields of a derived struct, which `#[remote(flatten)]` splices into the paths
+    /// of its container. Empty for other types.
+    const FIELDS: &'static [&'static str] = &[];
+
     fn parse_getter(x: &str) -> Option<Self> {
         if x.is_empty() {
             Some(Self::default())
@@ -80,20 +502,74 @@ pub trait Getter: Default + Hash + Eq + Clone + Copy + Display {
             None
         }
     }
+
+    /// Writes the numeric path of the getter (see `#[remote(id = ..)]`) into `ids`, returning the
+    /// number of ids written, or `None` if `ids` is too short or a field on the path has no id.
+    fn to_ids(&self, _ids: &mut [u32]) -> Option<usize> {
+        Some(0)
+    }
+
+    /// Like `parse_getter`, with the path given as ids, see `to_ids`.
+    fn parse_getter_ids(ids: &[u32]) -> Option<Self> {
+        if ids.is_empty() {
+            Some(Self::default())
+        } else {
+            None
+        }
+    }
+
+    /// Whether the getter is the empty path, which addresses the whole value.
+    #[doc(hidden)]
+    fn is_empty_path(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Used by `RemoteSet::reset`: converts the getter into the setter `S` resetting the value at its
+    /// path, segment by segment through `Setter::reset_in`. `None` if the path can't be reset.
+    #[doc(hidden)]
+    fn reset_setter<S: Setter>(&self) -> Option<S> {
+        if self.is_empty_path() {
+            S::reset_whole()
+        } else {
+            None
+        }
+    }
 }
 
 pub trait RemoteGet {
     type ValueType: Value;
     type GetterType: Getter;
 
-    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, ()> ;
+    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> ;
 
-    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), ()> ;
+    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> ;
 
     fn dynamic_getter(x: &str) -> Option<Self::GetterType>
     {
         Self::GetterType::parse_getter(x)
     }
+
+    fn id_getter(ids: &[u32]) -> Option<Self::GetterType>
+    {
+        Self::GetterType::parse_getter_ids(ids)
+    }
+
+    /// Like `Getter::get_fields`, but resolves the parts only known at runtime from `self`:
+    /// sequences report their current length as `FieldsType::Arr`, and maps pass each of their
+    /// keys (in path form, e.g. `"motor1"`) to `keys` before returning `FieldsType::Map`.
+    fn dynamic_fields(&self, x: &str, _keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+        Self::GetterType::get_fields(x)
+    }
+
+    /// Returns the metadata of the field at path `x`, or `None` if the path does not exist.
+    /// The derives fill in the attributes and type names of their fields and variants.
+    fn get_metadata(x: &str) -> Option<FieldMetadata> {
+        if x.is_empty() {
+            Some(FieldMetadata::default())
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Encode, Decode, Default, Hash, Eq, Clone, Copy, PartialEq, Debug)]
@@ -106,6 +582,10 @@ impl Value for () {
     fn dehydrate(&self, _x: &mut [u8]) -> Option<usize> {
         Some(0)
     }
+
+    fn dehydrated_len(&self) -> usize {
+        0
+    }
 }
 
 impl Display for NullSetter {
@@ -128,43 +608,54 @@ impl Display for NullGetter {
 
 impl Getter for NullGetter {}
 
+// `$wire` is the type used for dehydration, so types whose width depends on the platform
+// can use a fixed width on the wire
 macro_rules! impl_num_primitive {
     ($t:ty) => {
+        impl_num_primitive!($t, $t);
+    };
+    ($t:ty, $wire:ty) => {
         impl RemoteSet for $t {
             type SetterType = $t;
 
-            fn set(&mut self, x: Self::SetterType) -> Result<(), ()> {
+            fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
                 *self = x;
                 Ok(())
             }
+
+            impl_reset_leaf!();
         }
 
         impl RemoteGet for $t {
             type ValueType = Self;
             type GetterType = NullGetter;
 
-            fn get(&self, _: Self::GetterType) -> Result<Self::ValueType, ()> {
+            fn get(&self, _: Self::GetterType) -> Result<Self::ValueType, Error> {
                 Ok(*self)
             }
 
-            fn hydrate(_: NullGetter, buf: &[u8]) -> Result<(Self::ValueType, usize), ()> {
-                const SIZE: usize = size_of::<$t>();
+            fn hydrate(_: NullGetter, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+                const SIZE: usize = size_of::<$wire>();
                 if buf.len() < SIZE {
-                    return Err(());
+                    return Err(Error::BufferTooShort { depth: 0 });
                 }
-                Ok((<$t>::from_le_bytes(buf[0..SIZE].try_into().unwrap()), SIZE))
+                let v = <$wire>::from_le_bytes(buf[0..SIZE].try_into().unwrap());
+                let v = <$t>::try_from(v).map_err(|_| Error::InvalidEncoding { depth: 0 })?;
+                Ok((v, SIZE))
             }
         }
 
         impl Value for $t {
             fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
-                let buf = self.to_le_bytes();
-                for (idx, i) in buf.iter().enumerate() {
-                    x[idx] = *i;
-                }
+                let buf = (*self as $wire).to_le_bytes();
+                x.get_mut(..buf.len())?.copy_from_slice(&buf);
                 Some(buf.len())
             }
 
+            fn dehydrated_len(&self) -> usize {
+                size_of::<$wire>()
+            }
+
             fn as_float(&self) -> Option<f32> {
                 Some(*self as f32)
             }
@@ -186,15 +677,166 @@ impl_num_primitive!(u8);
 impl_num_primitive!(u16);
 impl_num_primitive!(u32);
 impl_num_primitive!(u64);
+impl_num_primitive!(u128);
+impl_num_primitive!(usize, u64);
 
 impl_num_primitive!(i8);
 impl_num_primitive!(i16);
 impl_num_primitive!(i32);
 impl_num_primitive!(i64);
+impl_num_primitive!(i128);
+impl_num_primitive!(isize, i64);
 
 impl_num_primitive!(f32);
 impl_num_primitive!(f64);
 
+impl RemoteSet for bool {
+    type SetterType = bool;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        *self = x;
+        Ok(())
+    }
+
+    impl_reset_leaf!();
+}
+
+impl RemoteGet for bool {
+    type ValueType = Self;
+    type GetterType = NullGetter;
+
+    fn get(&self, _: Self::GetterType) -> Result<Self::ValueType, Error> {
+        Ok(*self)
+    }
+
+    fn hydrate(_: NullGetter, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        match buf.first() {
+            Some(0) => Ok((false, 1)),
+            Some(1) => Ok((true, 1)),
+            Some(_) => Err(Error::InvalidEncoding { depth: 0 }),
+            None => Err(Error::BufferTooShort { depth: 0 }),
+        }
+    }
+}
+
+impl Value for bool {
+    fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
+        *x.first_mut()? = *self as u8;
+        Some(1)
+    }
+
+    fn dehydrated_len(&self) -> usize {
+        1
+    }
+
+    fn as_float(&self) -> Option<f32> {
+        Some(*self as u8 as f32)
+    }
+}
+
+impl Setter for bool {
+    fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
+        if x.is_empty() {
+            Some(set != 0.0)
+        } else {
+            None
+        }
+    }
+}
+
+impl RemoteSet for char {
+    type SetterType = char;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        *self = x;
+        Ok(())
+    }
+
+    impl_reset_leaf!();
+}
+
+impl RemoteGet for char {
+    type ValueType = Self;
+    type GetterType = NullGetter;
+
+    fn get(&self, _: Self::GetterType) -> Result<Self::ValueType, Error> {
+        Ok(*self)
+    }
+
+    // chars are dehydrated as utf-8, the length is given by the leading byte
+    fn hydrate(_: NullGetter, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        let len = match buf.first() {
+            Some(0x00..=0x7f) => 1,
+            Some(0xc0..=0xdf) => 2,
+            Some(0xe0..=0xef) => 3,
+            Some(0xf0..=0xf7) => 4,
+            Some(_) => return Err(Error::InvalidEncoding { depth: 0 }),
+            None => return Err(Error::BufferTooShort { depth: 0 }),
+        };
+        let bytes = buf.get(..len).ok_or(Error::BufferTooShort { depth: 0 })?;
+        let s = core::str::from_utf8(bytes).map_err(|_| Error::InvalidEncoding { depth: 0 })?;
+        match s.chars().next() {
+            Some(c) => Ok((c, len)),
+            None => Err(Error::InvalidEncoding { depth: 0 }),
+        }
+    }
+}
+
+impl Value for char {
+    fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
+        let len = self.len_utf8();
+        self.encode_utf8(x.get_mut(..len)?);
+        Some(len)
+    }
+
+    fn dehydrated_len(&self) -> usize {
+        self.len_utf8()
+    }
+
+    fn as_float(&self) -> Option<f32> {
+        Some(*self as u32 as f32)
+    }
+}
+
+impl Setter for char {
+    fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
+        if x.is_empty() && set >= 0.0 && set <= u32::MAX as f64 {
+            char::from_u32(set as u32)
+        } else {
+            None
+        }
+    }
+}
+
+/// Writes `id` followed by the ids written by `rest` into `ids`, see `Getter::to_ids`.
+fn push_id(ids: &mut [u32], id: u32, rest: impl FnOnce(&mut [u32]) -> Option<usize>) -> Option<usize> {
+    let (first, ids) = ids.split_first_mut()?;
+    *first = id;
+    Some(rest(ids)? + 1)
+}
+
+/// Splits a leading `[id]` off a numeric path, rejecting ids outside of `0..len` like `parse_index`.
+fn split_index(ids: &[u32], len: usize) -> Option<(usize, &[u32])> {
+    let (&idx, rest) = ids.split_first()?;
+    let idx = usize::try_from(idx).ok()?;
+    if idx >= len {
+        return None;
+    }
+    Some((idx, rest))
+}
+
+/// Splits a leading `[idx]` off a path, returning the index and the rest of the path.
+/// Indices outside of `0..len` are rejected.
+fn parse_index(x: &str, len: usize) -> Option<(usize, &str)> {
+    let x = x.strip_prefix('[')?;
+    let r_bracket = x.find(']')?;
+    let idx = x[..r_bracket].parse::<usize>().ok()?;
+    if idx >= len {
+        return None;
+    }
+    Some((idx, &x[r_bracket + 1..]))
+}
+
 #[derive(Debug, Encode, Decode, Clone, Hash, PartialEq, Eq, Copy)]
 pub struct ArrHelper<T, const N: usize> where T: Copy {
     r: T,
@@ -214,13 +856,33 @@ impl<T, const N: usize> RemoteSet for [T; N] where T: RemoteSet,
 {
     type SetterType = ArrHelper<T::SetterType, N>;
 
-    fn set(&mut self, x: Self::SetterType) -> Result<(), ()> {
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
         match self.get_mut(x.idx) {
             Some(v) => {
-                v.set(x.r)?;
-                Ok(())
+                v.set(x.r).map_err(Error::nested)
             }
-            None => Err(()),
+            None => Err(Error::IndexOutOfBounds { depth: 0 }),
+        }
+    }
+
+    fn reset_to_default(&mut self) -> Result<(), Error> {
+        // resets all elements, returning the first error
+        let mut result = Ok(());
+        for v in self.iter_mut() {
+            result = result.and(v.reset_to_default());
+        }
+        result
+    }
+
+    fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        for (idx, (v, other)) in self.iter().zip(other.iter()).enumerate() {
+            v.diff(other, &path.index(idx), changed);
+        }
+    }
+
+    fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        for (idx, v) in self.iter().enumerate() {
+            v.diff_default(&path.index(idx), changed);
         }
     }
 }
@@ -241,22 +903,46 @@ impl<T: Copy + Default + Display, const N: usize> Display for ArrHelper<T, N> {
 }
 
 impl<T: Copy + Default + Setter, const N: usize> Setter for ArrHelper<T, N> {
-    fn parse_setter<I: Sized>(&self, x: &str, set: I) -> Option<Self> {
-        let l_bracket = x.find('[')?;
-        let r_bracket = x.find(']')?;
-        let idx = x[l_bracket + 1..r_bracket].parse::<usize>().ok()?;
+    fn parse_setter<I: 'static>(&self, x: &str, set: I) -> Option<Self> {
+        let (idx, rest) = parse_index(x, N)?;
         Some(ArrHelper {
-            r: T::default().parse_setter(&x[r_bracket + 1..], set)?,
+            r: T::default().parse_setter(rest, set)?,
             idx,
         })
     }
 
     fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
-        let l_bracket = x.find('[')?;
-        let r_bracket = x.find(']')?;
-        let idx = x[l_bracket + 1..r_bracket].parse::<usize>().ok()?;
+        let (idx, rest) = parse_index(x, N)?;
         Some(ArrHelper {
-            r: T::default().parse_setter_numeric(&x[r_bracket + 1..], set)?,
+            r: T::default().parse_setter_numeric(rest, set)?,
+            idx,
+        })
+    }
+
+    fn reset_in<G: Getter>(segment: ResetSegment, inner: &G) -> Option<Self> {
+        match segment {
+            ResetSegment::Index(idx) => Some(ArrHelper::new(inner.reset_setter()?, idx)),
+            _ => None,
+        }
+    }
+
+    // elements are addressed by their index
+    fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+        push_id(ids, u32::try_from(self.idx).ok()?, |ids| self.r.to_ids(ids))
+    }
+
+    fn parse_setter_ids<I: 'static>(&self, ids: &[u32], set: I) -> Option<Self> {
+        let (idx, rest) = split_index(ids, N)?;
+        Some(ArrHelper {
+            r: T::default().parse_setter_ids(rest, set)?,
+            idx,
+        })
+    }
+
+    fn parse_setter_ids_numeric(&self, ids: &[u32], set: f64) -> Option<Self> {
+        let (idx, rest) = split_index(ids, N)?;
+        Some(ArrHelper {
+            r: T::default().parse_setter_ids_numeric(rest, set)?,
             idx,
         })
     }
@@ -267,18 +953,38 @@ impl<T, const N: usize> RemoteGet for [T; N] where T: RemoteGet,
     type ValueType = ArrHelper<T::ValueType, N>;
     type GetterType = ArrHelper<T::GetterType, N>;
 
-    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, ()> {
-        self[x.idx].get(x.r).map(|v| ArrHelper {
+    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+        let v = self.as_slice().get(x.idx).ok_or(Error::IndexOutOfBounds { depth: 0 })?;
+        v.get(x.r).map(|v| ArrHelper {
             r: v,
             idx: x.idx,
-        })
+        }).map_err(Error::nested)
     }
 
-    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), ()> {
+    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        if x.idx >= N {
+            return Err(Error::IndexOutOfBounds { depth: 0 });
+        }
         T::hydrate(x.r, buf).map(|(v, s)| (ArrHelper {
             r: v,
             idx: x.idx,
-        }, s))
+        }, s)).map_err(Error::nested)
+    }
+
+    fn dynamic_fields(&self, x: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+        if x.is_empty() {
+            return Some(FieldsType::Arr(N))
+        }
+        let (idx, rest) = parse_index(x, N)?;
+        self[idx].dynamic_fields(rest, keys)
+    }
+
+    fn get_metadata(x: &str) -> Option<FieldMetadata> {
+        if x.is_empty() {
+            return Some(FieldMetadata::default())
+        }
+        let (_, rest) = parse_index(x, N)?;
+        T::get_metadata(rest)
     }
 }
 
@@ -287,16 +993,18 @@ impl<T: Copy + Value, const N: usize> Value for ArrHelper<T, N> {
         self.r.dehydrate(x)
     }
 
+    fn dehydrated_len(&self) -> usize {
+        self.r.dehydrated_len()
+    }
+
     fn as_float(&self) -> Option<f32> {
         self.r.as_float()
     }
 
-    fn parse_value<I: Sized>(self, x: &str) -> Option<I> {
-        let l_bracket = x.find('[')?;
-        let r_bracket = x.find(']')?;
-        let idx = x[l_bracket + 1..r_bracket].parse::<usize>().ok()?;
+    fn parse_value<I: 'static>(self, x: &str) -> Option<I> {
+        let (idx, rest) = parse_index(x, N)?;
         if idx == self.idx {
-            Some(self.r.parse_value(&x[r_bracket + 1..])?)
+            Some(self.r.parse_value(rest)?)
         } else {
             None
         }
@@ -305,11 +1013,9 @@ impl<T: Copy + Value, const N: usize> Value for ArrHelper<T, N> {
 
 impl<T: Copy + Default + Getter, const N: usize> Getter for ArrHelper<T, N> {
     fn parse_getter(x: &str) -> Option<Self> {
-        let l_bracket = x.find('[')?;
-        let r_bracket = x.find(']')?;
-        let idx = x[l_bracket + 1..r_bracket].parse::<usize>().ok()?;
+        let (idx, rest) = parse_index(x, N)?;
         Some(ArrHelper {
-            r: T::parse_getter(&x[r_bracket + 1..])?,
+            r: T::parse_getter(rest)?,
             idx,
         })
     }
@@ -318,15 +1024,29 @@ impl<T: Copy + Default + Getter, const N: usize> Getter for ArrHelper<T, N> {
         if x.is_empty() {
             return Some(FieldsType::Arr(N))
         }
-        let l_bracket = x.find('[')?;
-        let r_bracket = x.find(']')?;
-        let idx = x[l_bracket + 1..r_bracket].parse::<usize>().ok()?;
+        let (_, rest) = parse_index(x, N)?;
+        T::get_fields(rest)
+    }
 
-        if idx >= N {
-            return None;
-        } else {
-            T::get_fields(&x[r_bracket + 1..])
-        }
+    fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+        push_id(ids, u32::try_from(self.idx).ok()?, |ids| self.r.to_ids(ids))
+    }
+
+    fn parse_getter_ids(ids: &[u32]) -> Option<Self> {
+        let (idx, rest) = split_index(ids, N)?;
+        Some(ArrHelper {
+            r: T::parse_getter_ids(rest)?,
+            idx,
+        })
+    }
+
+    // always addresses an element, also with the default index 0
+    fn is_empty_path(&self) -> bool {
+        false
+    }
+
+    fn reset_setter<S: Setter>(&self) -> Option<S> {
+        S::reset_in(ResetSegment::Index(self.idx), &self.r)
     }
 }
 
@@ -336,7 +1056,7 @@ impl<T: Copy, const N: usize> Index<usize> for ArrHelper<T, N> {
 
     fn index(&self, index: usize) -> &Self::Output {
         assert_eq!(index, self.idx);
-        return &self.r;
+        &self.r
     }
 }
 
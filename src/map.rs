@@ -0,0 +1,391 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Index;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use bincode::{Encode, Decode};
+use crate::{Error, FieldMetadata, FieldPath, FieldsType, Getter, NullGetter, RemoteGet, RemoteSet, ResetSegment, Setter, StringBuf, Value};
+
+/// Maximum length in bytes of a map key in path form (e.g. `"motor1"`, including the quotes)
+/// that can be stored in a getter or setter.
+pub const KEY_LEN: usize = 32;
+
+/// Keys of a `BTreeMap`, addressed in paths as `["motor1"]` for strings and `[3]` for integers.
+pub trait MapKey: Ord + Sized {
+    /// Parses the key from its path form, the text between the brackets.
+    fn from_path(x: &str) -> Option<Self>;
+    /// Returns the key in path form.
+    fn to_path(&self) -> String;
+}
+
+impl MapKey for String {
+    /// Strings are quoted and escaped as written by `Debug`.
+    fn from_path(x: &str) -> Option<Self> {
+        let mut chars = x.strip_prefix('"')?.strip_suffix('"')?.chars();
+        let mut s = String::new();
+        while let Some(c) = chars.next() {
+            let c = match c {
+                '"' => return None,
+                '\\' => match chars.next()? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    c @ ('\\' | '"' | '\'') => c,
+                    'u' => {
+                        let (hex, rest) = chars.as_str().strip_prefix('{')?.split_once('}')?;
+                        chars = rest.chars();
+                        char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+                    }
+                    _ => return None,
+                },
+                c => c,
+            };
+            s.push(c);
+        }
+        Some(s)
+    }
+
+    fn to_path(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+macro_rules! impl_map_key_int {
+    ($($t:ty),*) => {
+        $(impl MapKey for $t {
+            fn from_path(x: &str) -> Option<Self> {
+                x.parse().ok()
+            }
+
+            fn to_path(&self) -> String {
+                format!("{}", self)
+            }
+        })*
+    }
+}
+
+impl_map_key_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Splits a leading `[key]` off a path, returning the key in path form and the rest of the path.
+fn parse_key(x: &str) -> Option<(StringBuf<KEY_LEN>, &str)> {
+    let x = x.strip_prefix('[')?;
+    let end = if let Some(quoted) = x.strip_prefix('"') {
+        // the first quote that isn't escaped
+        let mut escaped = false;
+        let (end, _) = quoted.char_indices().find(|&(_, c)| {
+            let end = c == '"' && !escaped;
+            escaped = c == '\\' && !escaped;
+            end
+        })?;
+        end + 2
+    } else {
+        x.find(']')?
+    };
+    let rest = x[end..].strip_prefix(']')?;
+    Some((StringBuf::new(&x[..end])?, rest))
+}
+
+/// Key in path form, as written by `Debug` (quoted for strings). Keys longer than `KEY_LEN` bytes
+/// give an overflowed `StringBuf`, which the map rejects with `Error::CapacityExceeded`.
+fn key_from_debug<K: Debug>(key: K) -> StringBuf<KEY_LEN> {
+    StringBuf::copy(&format!("{:?}", key))
+}
+
+/// Getter for a map, addressing either the value of a key with `["key"]` or the number of entries
+/// with `.len`.
+#[derive(Debug, Encode, Decode, Default, Clone, Hash, PartialEq, Eq, Copy)]
+pub enum MapGetter<G> {
+    Key(StringBuf<KEY_LEN>, G),
+    Len,
+    #[default]
+    Empty,
+}
+
+/// Value for a map, see `MapGetter`.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Copy)]
+pub enum MapValue<V> {
+    Key(StringBuf<KEY_LEN>, V),
+    Len(usize),
+}
+
+/// Setter for a map, writing into the value of an existing key.
+#[derive(Debug, Encode, Decode, Default, Clone, Hash, PartialEq, Eq, Copy)]
+pub enum MapSetter<S> {
+    Key(StringBuf<KEY_LEN>, S),
+    #[default]
+    Empty,
+}
+
+impl<G: Getter> Display for MapGetter<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MapGetter::Key(key, g) => write!(f, "[{}]{}", key.as_str(), g),
+            MapGetter::Len => write!(f, ".len"),
+            MapGetter::Empty => Ok(()),
+        }
+    }
+}
+
+impl<G: Getter> Getter for MapGetter<G> {
+    fn parse_getter(x: &str) -> Option<Self> {
+        if x == ".len" {
+            return Some(MapGetter::Len);
+        }
+        let (key, rest) = parse_key(x)?;
+        Some(MapGetter::Key(key, G::parse_getter(rest)?))
+    }
+
+    fn get_fields(x: &str) -> Option<FieldsType> {
+        match x {
+            "" => Some(FieldsType::Map),
+            ".len" => Some(FieldsType::Terminal),
+            x => G::get_fields(parse_key(x)?.1),
+        }
+    }
+
+    // keys can be strings, so maps have no numeric paths
+    fn to_ids(&self, _ids: &mut [u32]) -> Option<usize> {
+        None
+    }
+
+    fn parse_getter_ids(_ids: &[u32]) -> Option<Self> {
+        None
+    }
+
+    fn reset_setter<S: Setter>(&self) -> Option<S> {
+        match self {
+            MapGetter::Key(key, g) => S::reset_in(ResetSegment::Key(*key), g),
+            MapGetter::Len => None,
+            MapGetter::Empty => S::reset_whole(),
+        }
+    }
+}
+
+impl<G: Getter> MapGetter<G> {
+    pub fn arr_get<K: Debug, F>(self, key: K, func: F) -> Self where F: Fn(G) -> G {
+        MapGetter::Key(key_from_debug(key), func(G::default()))
+    }
+
+    pub fn make_len<F>(&self, _func: F) -> Self where F: Fn(NullGetter) -> NullGetter {
+        MapGetter::Len
+    }
+}
+
+impl<V: Value> MapValue<V> {
+    /// Returns the number of entries, panics if this is not the result of a `.len` getter.
+    pub fn len(self) -> usize {
+        match self {
+            MapValue::Len(len) => len,
+            _ => panic!("MapValue::len called on an entry value"),
+        }
+    }
+
+    /// Whether the map read with `.len` is empty, panics like `len`.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of `key`, or `None` if this is the value of another key or a length.
+    pub fn get<K: Debug>(&self, key: K) -> Option<&V> {
+        match self {
+            MapValue::Key(k, v) if format!("{:?}", key) == k.as_str() => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<V: Value, K: Debug> Index<K> for MapValue<V> {
+    type Output = V;
+
+    /// Panics if this is not the value of `key`, see `get`.
+    fn index(&self, key: K) -> &Self::Output {
+        self.get(key).expect("MapValue indexed with another key or on a length value")
+    }
+}
+
+impl<V: Value> Value for MapValue<V> {
+    fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
+        match self {
+            MapValue::Key(_, v) => v.dehydrate(x),
+            MapValue::Len(len) => len.dehydrate(x),
+        }
+    }
+
+    fn dehydrated_len(&self) -> usize {
+        match self {
+            MapValue::Key(_, v) => v.dehydrated_len(),
+            MapValue::Len(len) => len.dehydrated_len(),
+        }
+    }
+
+    fn as_float(&self) -> Option<f32> {
+        match self {
+            MapValue::Key(_, v) => v.as_float(),
+            MapValue::Len(len) => len.as_float(),
+        }
+    }
+
+    fn parse_value<T: 'static>(self, x: &str) -> Option<T> {
+        match self {
+            MapValue::Key(key, v) => {
+                let (x_key, rest) = parse_key(x)?;
+                if x_key == key {
+                    v.parse_value(rest)
+                } else {
+                    None
+                }
+            }
+            MapValue::Len(len) => len.parse_value(x.strip_prefix(".len")?),
+        }
+    }
+}
+
+impl<S: Setter> Display for MapSetter<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MapSetter::Key(key, s) => write!(f, "[{}]{}", key.as_str(), s),
+            MapSetter::Empty => Ok(()),
+        }
+    }
+}
+
+impl<S: Setter> Setter for MapSetter<S> {
+    fn parse_setter<T: 'static>(&self, x: &str, set: T) -> Option<Self> {
+        let (key, rest) = parse_key(x)?;
+        Some(MapSetter::Key(key, S::default().parse_setter(rest, set)?))
+    }
+
+    fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
+        let (key, rest) = parse_key(x)?;
+        Some(MapSetter::Key(key, S::default().parse_setter_numeric(rest, set)?))
+    }
+
+    fn to_ids(&self, _ids: &mut [u32]) -> Option<usize> {
+        None
+    }
+
+    fn parse_setter_ids<T: 'static>(&self, _ids: &[u32], _set: T) -> Option<Self> {
+        None
+    }
+
+    fn parse_setter_ids_numeric(&self, _ids: &[u32], _set: f64) -> Option<Self> {
+        None
+    }
+
+    fn reset_in<G: Getter>(segment: ResetSegment, inner: &G) -> Option<Self> {
+        match segment {
+            ResetSegment::Key(key) => Some(MapSetter::Key(key, inner.reset_setter()?)),
+            _ => None,
+        }
+    }
+}
+
+impl<S: Setter> MapSetter<S> {
+    pub fn arr_set<K: Debug, F>(self, key: K, func: F) -> Self where F: Fn(S) -> S {
+        MapSetter::Key(key_from_debug(key), func(S::default()))
+    }
+}
+
+fn parse_map_key<K: MapKey>(key: StringBuf<KEY_LEN>) -> Result<K, Error> {
+    key.check()?;
+    K::from_path(key.as_str()).ok_or(Error::KeyNotFound { depth: 0 })
+}
+
+/// Parses a key and rewrites it in the form `MapKey::to_path` gives, so an entry read through another spelling
+/// (e.g. `["\u{61}"]` for `["a"]`) is returned under the same key `dynamic_fields` reports.
+fn canonical_key<K: MapKey>(key: StringBuf<KEY_LEN>) -> Result<(K, StringBuf<KEY_LEN>), Error> {
+    let k = parse_map_key::<K>(key)?;
+    let key = StringBuf::copy(&k.to_path());
+    key.check()?;
+    Ok((k, key))
+}
+
+impl<K: MapKey, V: RemoteSet> RemoteSet for BTreeMap<K, V> {
+    type SetterType = MapSetter<V::SetterType>;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        match x {
+            MapSetter::Key(key, s) => {
+                let v = self.get_mut(&parse_map_key(key)?).ok_or(Error::KeyNotFound { depth: 0 })?;
+                v.set(s).map_err(Error::nested)
+            }
+            MapSetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn reset_to_default(&mut self) -> Result<(), Error> {
+        self.clear();
+        Ok(())
+    }
+
+    // maps with different keys are reported as a whole, like sequences of different lengths
+    fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        if !self.keys().eq(other.keys()) {
+            return changed(path);
+        }
+        for ((k, v), other) in self.iter().zip(other.values()) {
+            let key = format!("[{}]", k.to_path());
+            v.diff(other, &path.field(&key), changed);
+        }
+    }
+
+    fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        if !self.is_empty() {
+            changed(path);
+        }
+    }
+}
+
+impl<K: MapKey, V: RemoteGet> RemoteGet for BTreeMap<K, V> {
+    type ValueType = MapValue<V::ValueType>;
+    type GetterType = MapGetter<V::GetterType>;
+
+    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+        match x {
+            MapGetter::Key(key, g) => {
+                let (k, key) = canonical_key::<K>(key)?;
+                let v = self.get(&k).ok_or(Error::KeyNotFound { depth: 0 })?;
+                v.get(g).map(|v| MapValue::Key(key, v)).map_err(Error::nested)
+            }
+            MapGetter::Len => Ok(MapValue::Len(self.len())),
+            MapGetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        match x {
+            MapGetter::Key(key, g) => {
+                let (_, key) = canonical_key::<K>(key)?;
+                V::hydrate(g, buf).map(|(v, len)| (MapValue::Key(key, v), len)).map_err(Error::nested)
+            }
+            MapGetter::Len => usize::hydrate(NullGetter, buf).map(|(v, len)| (MapValue::Len(v), len)).map_err(Error::nested),
+            MapGetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn dynamic_fields(&self, x: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+        match x {
+            "" => {
+                for k in self.keys() {
+                    keys(&k.to_path());
+                }
+                Some(FieldsType::Map)
+            }
+            ".len" => Some(FieldsType::Terminal),
+            x => {
+                let (key, rest) = parse_key(x)?;
+                self.get(&K::from_path(key.as_str())?)?.dynamic_fields(rest, keys)
+            }
+        }
+    }
+
+    fn get_metadata(x: &str) -> Option<FieldMetadata> {
+        match x {
+            "" => Some(FieldMetadata::default()),
+            ".len" => Some(FieldMetadata { type_name: "usize", ..FieldMetadata::default() }),
+            x => V::get_metadata(parse_key(x)?.1),
+        }
+    }
+}
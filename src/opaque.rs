@@ -0,0 +1,157 @@
+use core::any::Any;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use bincode::{Encode, Decode};
+use bincode::config::{standard, Configuration};
+use bincode::enc::write::{SizeWriter, Writer};
+use bincode::error::{DecodeError, EncodeError};
+use crate::{downcast, Error, NullGetter, Proxy, ProxyMut, RemoteGet, RemoteSet, Setter, SetterFrom, Value};
+
+const CONFIG: Configuration = standard();
+
+/// Leaf holding a value which is read and written as a whole, dehydrated with bincode (standard
+/// configuration). Fields of type `T` can also be marked with `#[remote(opaque)]` instead.
+///
+/// Any `T: Encode + Decode + Copy` can be read and written. As `T` isn't required to implement
+/// `Default` or `PartialEq`, opaque values can't be reset and are always reported by `diff`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Opaque<T>(pub T);
+
+/// Setter of an `Opaque<T>`, writing the whole value.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpaqueSetter<T> {
+    Set(T),
+    #[default]
+    Empty,
+}
+
+impl<T: Copy> Proxy<T> for Opaque<T> {
+    fn from_field(x: &T) -> Self {
+        Opaque(*x)
+    }
+}
+
+impl<T: Copy> ProxyMut<T> for Opaque<T> {
+    fn from_field(x: &T) -> Self {
+        Opaque(*x)
+    }
+
+    fn into_field(self) -> T {
+        self.0
+    }
+}
+
+/// Writes the bytes as hex to a formatter.
+struct HexWriter<'a, 'b>(&'a mut Formatter<'b>);
+
+impl Writer for HexWriter<'_, '_> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        for byte in bytes {
+            write!(self.0, "{:02x}", byte).map_err(|_| EncodeError::Other("formatter error"))?;
+        }
+        Ok(())
+    }
+}
+
+/// As the value can't be printed without `Display`, setters show its encoding in hex.
+impl<T: Encode> Display for OpaqueSetter<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OpaqueSetter::Set(x) => {
+                write!(f, "0x")?;
+                bincode::encode_into_writer(x, HexWriter(f), CONFIG).map_err(|_| fmt::Error)
+            }
+            OpaqueSetter::Empty => Ok(()),
+        }
+    }
+}
+
+impl<T: Encode + Copy + 'static> SetterFrom<T> for OpaqueSetter<T> {
+    fn setter_from(x: T) -> Self {
+        OpaqueSetter::Set(x)
+    }
+}
+
+impl<T: Encode + Copy + 'static> SetterFrom<Opaque<T>> for OpaqueSetter<T> {
+    fn setter_from(x: Opaque<T>) -> Self {
+        OpaqueSetter::Set(x.0)
+    }
+}
+
+impl<T: Encode + Decode<()> + Copy + 'static> RemoteGet for Opaque<T> {
+    type ValueType = Self;
+    type GetterType = NullGetter;
+
+    fn get(&self, _: Self::GetterType) -> Result<Self::ValueType, Error> {
+        Ok(*self)
+    }
+
+    fn hydrate(_: NullGetter, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        match bincode::decode_from_slice(buf, CONFIG) {
+            Ok((v, len)) => Ok((Opaque(v), len)),
+            Err(DecodeError::UnexpectedEnd { .. }) => Err(Error::BufferTooShort { depth: 0 }),
+            Err(_) => Err(Error::InvalidEncoding { depth: 0 }),
+        }
+    }
+}
+
+impl<T: Encode + Decode<()> + Copy + 'static> RemoteSet for Opaque<T> {
+    type SetterType = OpaqueSetter<T>;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        match x {
+            OpaqueSetter::Set(x) => {
+                self.0 = x;
+                Ok(())
+            }
+            OpaqueSetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+}
+
+impl<T: Encode + Copy + 'static> Value for Opaque<T> {
+    fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
+        bincode::encode_into_slice(self.0, x, CONFIG).ok()
+    }
+
+    fn dehydrated_len(&self) -> usize {
+        let mut size = SizeWriter::default();
+        // counting can't fail, only `Encode` impls could
+        bincode::encode_into_writer(self.0, &mut size, CONFIG).map_or(0, |_| size.bytes_written)
+    }
+
+    /// Returns the value as a `T` or an `Opaque<T>`.
+    fn parse_value<U: 'static>(self, x: &str) -> Option<U> {
+        if !x.is_empty() {
+            return None;
+        }
+        downcast(self.0).or_else(|| downcast(self))
+    }
+}
+
+impl<T: Encode + Copy + 'static> Setter for OpaqueSetter<T> {
+    /// Accepts a `T` or an `Opaque<T>`.
+    fn parse_setter<U: 'static>(&self, x: &str, set: U) -> Option<Self> {
+        if !x.is_empty() {
+            return None;
+        }
+        let mut set = Some(set);
+        let set = &mut set as &mut dyn Any;
+        if let Some(x) = set.downcast_mut::<Option<T>>() {
+            return x.take().map(OpaqueSetter::Set);
+        }
+        set.downcast_mut::<Option<Opaque<T>>>()?.take().map(|x| OpaqueSetter::Set(x.0))
+    }
+
+    fn parse_setter_numeric(&self, _x: &str, _set: f64) -> Option<Self> {
+        None
+    }
+
+    /// Returns the value as an `Opaque<T>`.
+    fn into_value<U: 'static>(self) -> Option<U> {
+        match self {
+            OpaqueSetter::Set(x) => downcast(Opaque(x)),
+            OpaqueSetter::Empty => None,
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use bincode::{Encode, Decode};
+use crate::{push_id, Error, FieldMetadata, FieldPath, FieldsType, Getter, NullGetter, RemoteGet, RemoteSet, ResetSegment, Setter, SetterFrom, Value};
+
+/// Id of `::None` in numeric paths, see `Getter::to_ids`.
+const NONE_ID: u32 = 0;
+/// Id of `::Some` in numeric paths.
+const SOME_ID: u32 = 1;
+/// Index of `::Some` in the declaration of `Option`, see `ResetSegment::Field`.
+const SOME_INDEX: usize = 1;
+
+/// Getter for `Option<T>`, addressed like an enum with `::Some` and `VARIANT`.
+#[derive(Debug, Encode, Decode, Default, Clone, Hash, PartialEq, Eq, Copy)]
+pub enum OptionGetter<G> {
+    GetVariant,
+    Some(G),
+    #[default]
+    Empty,
+}
+
+/// Value for `Option<T>`, `None` and `Some` are the result of `GetVariant`.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Copy)]
+pub enum OptionValue<V> {
+    SomeValue(V),
+    None,
+    Some,
+}
+
+/// Setter for `Option<T>`.
+///
+/// `None` clears the option, `Some` writes into the inner value if it is present and `Insert`
+/// replaces the option with the value of a setter writing a whole value (see `Setter::into_value`).
+/// For leaf types this means `setter!(Config.timeout = Some(5))` and
+/// `setter!(Config.timeout::Some = 5)` only differ in whether the option has to be present already.
+/// Other setters can't build a value, so they write into the present value like `Some`.
+#[derive(Debug, Encode, Decode, Default, Clone, Hash, PartialEq, Eq, Copy)]
+pub enum OptionSetter<S> {
+    None,
+    Some(S),
+    Insert(S),
+    #[default]
+    Empty,
+}
+
+impl<T: RemoteSet + 'static> RemoteSet for Option<T> {
+    type SetterType = OptionSetter<T::SetterType>;
+
+    fn set(&mut self, x: Self::SetterType) -> Result<(), Error> {
+        match x {
+            OptionSetter::None => {
+                *self = None;
+                Ok(())
+            }
+            OptionSetter::Some(s) => match self {
+                Some(inner) => inner.set(s).map_err(Error::nested),
+                None => Err(Error::WrongVariant { depth: 0 }),
+            },
+            OptionSetter::Insert(s) => match (s.into_value::<T>(), self) {
+                (Some(v), this) => {
+                    *this = Some(v);
+                    Ok(())
+                }
+                (None, Some(inner)) => inner.set(s).map_err(Error::nested),
+                (None, None) => Err(Error::WrongVariant { depth: 0 }),
+            },
+            OptionSetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn reset_to_default(&mut self) -> Result<(), Error> {
+        *self = None;
+        Ok(())
+    }
+
+    fn diff(&self, other: &Self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        match (self, other) {
+            (Some(v), Some(other)) => v.diff(other, &path.field("::Some"), changed),
+            (None, None) => {}
+            _ => changed(path),
+        }
+    }
+
+    fn diff_default(&self, path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+        if self.is_some() {
+            changed(path);
+        }
+    }
+}
+
+impl<S: Setter> Display for OptionSetter<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionSetter::None => write!(f, " = ::None"),
+            OptionSetter::Some(s) => write!(f, "::Some{}", s),
+            OptionSetter::Insert(s) => write!(f, " = Some({})", s),
+            OptionSetter::Empty => Ok(()),
+        }
+    }
+}
+
+impl<S: Setter> Setter for OptionSetter<S> {
+    fn parse_setter<T: 'static>(&self, x: &str, set: T) -> Option<Self> {
+        match x {
+            "" => match crate::downcast::<T, Option<S>>(set)? {
+                Some(set) => Some(OptionSetter::Insert(set)),
+                None => Some(OptionSetter::None),
+            },
+            "::None" => {
+                crate::downcast::<T, ()>(set)?;
+                Some(OptionSetter::None)
+            }
+            x => Some(OptionSetter::Some(S::default().parse_setter(x.strip_prefix("::Some")?, set)?)),
+        }
+    }
+
+    fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
+        match x {
+            "" => Some(OptionSetter::Insert(S::default().parse_setter_numeric("", set)?)),
+            x => Some(OptionSetter::Some(S::default().parse_setter_numeric(x.strip_prefix("::Some")?, set)?)),
+        }
+    }
+
+    // `::None` and `::Some` have the ids 0 and 1, `Insert` is the empty path like in `parse_setter`
+    fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+        match self {
+            OptionSetter::None => push_id(ids, NONE_ID, |_| Some(0)),
+            OptionSetter::Some(s) => push_id(ids, SOME_ID, |ids| s.to_ids(ids)),
+            OptionSetter::Insert(_) => Some(0),
+            OptionSetter::Empty => None,
+        }
+    }
+
+    fn parse_setter_ids<T: 'static>(&self, ids: &[u32], set: T) -> Option<Self> {
+        match ids {
+            [] => self.parse_setter("", set),
+            [NONE_ID] => self.parse_setter("::None", set),
+            [SOME_ID, rest @ ..] => Some(OptionSetter::Some(S::default().parse_setter_ids(rest, set)?)),
+            _ => None,
+        }
+    }
+
+    fn parse_setter_ids_numeric(&self, ids: &[u32], set: f64) -> Option<Self> {
+        match ids {
+            [] => self.parse_setter_numeric("", set),
+            [SOME_ID, rest @ ..] => Some(OptionSetter::Some(S::default().parse_setter_ids_numeric(rest, set)?)),
+            _ => None,
+        }
+    }
+
+    fn reset_in<G: Getter>(segment: ResetSegment, inner: &G) -> Option<Self> {
+        match segment {
+            ResetSegment::Field(SOME_INDEX) => Some(OptionSetter::Some(inner.reset_setter()?)),
+            _ => None,
+        }
+    }
+}
+
+impl<S: Setter> SetterFrom<Option<S>> for OptionSetter<S> {
+    fn setter_from(x: Option<S>) -> Self {
+        match x {
+            Some(x) => OptionSetter::Insert(x),
+            None => OptionSetter::None,
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+impl<S: Setter> OptionSetter<S> {
+    pub fn make_None<F>(&self, _func: F) -> Self where F: Fn(()) {
+        OptionSetter::None
+    }
+
+    pub fn make_Some<F>(&self, func: F) -> Self where F: Fn(S) -> S {
+        OptionSetter::Some(func(S::default()))
+    }
+}
+
+impl<T: RemoteGet> RemoteGet for Option<T> {
+    type ValueType = OptionValue<T::ValueType>;
+    type GetterType = OptionGetter<T::GetterType>;
+
+    fn get(&self, x: Self::GetterType) -> Result<Self::ValueType, Error> {
+        match x {
+            OptionGetter::GetVariant => match self {
+                Some(_) => Ok(OptionValue::Some),
+                None => Ok(OptionValue::None),
+            },
+            OptionGetter::Some(g) => match self {
+                Some(inner) => Ok(OptionValue::SomeValue(inner.get(g).map_err(Error::nested)?)),
+                None => Err(Error::WrongVariant { depth: 0 }),
+            },
+            OptionGetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn hydrate(x: Self::GetterType, buf: &[u8]) -> Result<(Self::ValueType, usize), Error> {
+        match x {
+            OptionGetter::GetVariant => match buf.first() {
+                Some(0) => Ok((OptionValue::None, 1)),
+                Some(1) => Ok((OptionValue::Some, 1)),
+                Some(_) => Err(Error::InvalidEncoding { depth: 0 }),
+                None => Err(Error::BufferTooShort { depth: 0 }),
+            },
+            OptionGetter::Some(g) => {
+                let (v, len) = T::hydrate(g, buf).map_err(Error::nested)?;
+                Ok((OptionValue::SomeValue(v), len))
+            }
+            OptionGetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+        }
+    }
+
+    fn dynamic_fields(&self, x: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+        match (x.strip_prefix("::Some"), self) {
+            (Some(rest), Some(inner)) => inner.dynamic_fields(rest, keys),
+            _ => Self::GetterType::get_fields(x),
+        }
+    }
+
+    fn get_metadata(x: &str) -> Option<FieldMetadata> {
+        match x {
+            "" | "::None" => Some(FieldMetadata::default()),
+            x => T::get_metadata(x.strip_prefix("::Some")?),
+        }
+    }
+}
+
+impl<G: Getter> Display for OptionGetter<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionGetter::GetVariant => write!(f, "VARIANT"),
+            OptionGetter::Some(g) => write!(f, "::Some{}", g),
+            OptionGetter::Empty => Ok(()),
+        }
+    }
+}
+
+impl<G: Getter> Getter for OptionGetter<G> {
+    fn parse_getter(x: &str) -> Option<Self> {
+        match x {
+            "VARIANT" => Some(OptionGetter::GetVariant),
+            x => Some(OptionGetter::Some(G::parse_getter(x.strip_prefix("::Some")?)?)),
+        }
+    }
+
+    fn get_fields(x: &str) -> Option<FieldsType> {
+        match x {
+            "" => Some(FieldsType::Fields(&["::Some", "::None", "VARIANT"])),
+            "::None" => Some(FieldsType::Fields(&[])),
+            x => G::get_fields(x.strip_prefix("::Some")?),
+        }
+    }
+
+    // like for enums, the empty path reads the variant
+    fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+        match self {
+            OptionGetter::GetVariant => Some(0),
+            OptionGetter::Some(g) => push_id(ids, SOME_ID, |ids| g.to_ids(ids)),
+            OptionGetter::Empty => None,
+        }
+    }
+
+    fn parse_getter_ids(ids: &[u32]) -> Option<Self> {
+        match ids {
+            [] => Some(OptionGetter::GetVariant),
+            [SOME_ID, rest @ ..] => Some(OptionGetter::Some(G::parse_getter_ids(rest)?)),
+            _ => None,
+        }
+    }
+
+    fn reset_setter<S: Setter>(&self) -> Option<S> {
+        match self {
+            OptionGetter::GetVariant => None,
+            OptionGetter::Some(g) => S::reset_in(ResetSegment::Field(SOME_INDEX), g),
+            OptionGetter::Empty => S::reset_whole(),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+impl<G: Getter> OptionGetter<G> {
+    pub fn make_var<F>(&self, _func: F) -> Self where F: Fn(()) -> NullGetter {
+        OptionGetter::GetVariant
+    }
+
+    pub fn make_Some<F>(&self, func: F) -> Self where F: Fn(G) -> G {
+        OptionGetter::Some(func(G::default()))
+    }
+}
+
+#[allow(non_snake_case)]
+impl<V: Value> OptionValue<V> {
+    /// Returns the inner value, panics if this is not a `SomeValue`.
+    pub fn Some(self) -> V {
+        match self {
+            OptionValue::SomeValue(v) => v,
+            _ => panic!("OptionValue::Some called on a variant value"),
+        }
+    }
+
+    /// Whether the option was present when the value was read.
+    pub fn is_some(&self) -> bool {
+        matches!(self, OptionValue::Some | OptionValue::SomeValue(_))
+    }
+}
+
+impl<V: Value> Value for OptionValue<V> {
+    fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
+        match self {
+            OptionValue::SomeValue(v) => v.dehydrate(x),
+            OptionValue::None => {
+                *x.first_mut()? = 0;
+                Some(1)
+            }
+            OptionValue::Some => {
+                *x.first_mut()? = 1;
+                Some(1)
+            }
+        }
+    }
+
+    fn dehydrated_len(&self) -> usize {
+        match self {
+            OptionValue::SomeValue(v) => v.dehydrated_len(),
+            _ => 1,
+        }
+    }
+
+    fn as_float(&self) -> Option<f32> {
+        match self {
+            OptionValue::SomeValue(v) => v.as_float(),
+            OptionValue::None => Some(0.0),
+            OptionValue::Some => Some(1.0),
+        }
+    }
+
+    fn parse_value<T: 'static>(self, x: &str) -> Option<T> {
+        match self {
+            OptionValue::SomeValue(v) => v.parse_value(x.strip_prefix("::Some")?),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use bincode::{Encode, Decode, BorrowDecode};
+use bincode::de::{BorrowDecoder, Decoder};
+use bincode::de::read::Reader;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use crate::{downcast, Error, Setter, Value};
+
+/// Copy of a string of at most `N` bytes, used as the setter and value of string fields and as
+/// the key of map getters and setters.
+///
+/// It is dehydrated as the length in bytes (`u16`, little endian) followed by the UTF-8 bytes.
+///
+/// Setters built by `getter!`/`setter!` from a string that doesn't fit are marked as overflowed
+/// instead of panicking: they are empty, fail to encode, and are rejected with
+/// `Error::CapacityExceeded` when applied.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StringBuf<const N: usize> {
+    len: usize,
+    buf: [u8; N],
+    overflow: bool,
+}
+
+impl<const N: usize> StringBuf<N> {
+    /// Copies `s`, returns `None` if it is longer than `N` bytes.
+    pub fn new(s: &str) -> Option<Self> {
+        let mut buf = [0; N];
+        buf.get_mut(..s.len())?.copy_from_slice(s.as_bytes());
+        Some(StringBuf { len: s.len(), buf, overflow: false })
+    }
+
+    /// Copies `s`, or returns an empty buffer marked as overflowed if it is longer than `N` bytes.
+    pub(crate) fn copy(s: &str) -> Self {
+        StringBuf::new(s).unwrap_or(StringBuf { overflow: true, ..StringBuf::default() })
+    }
+
+    /// Whether the string this was built from didn't fit, see `copy`.
+    pub fn is_overflow(&self) -> bool {
+        self.overflow
+    }
+
+    /// Returns `Error::CapacityExceeded` if the buffer is overflowed.
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        match self.overflow {
+            true => Err(Error::CapacityExceeded { depth: 0 }),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the string, which is empty if the buffer is overflowed.
+    pub fn as_str(&self) -> &str {
+        // only constructed from valid strings, see `new`, `decode` and `hydrate`
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+
+    pub(crate) fn hydrate(buf: &[u8]) -> Result<(Self, usize), Error> {
+        let len = buf.get(..2).ok_or(Error::BufferTooShort { depth: 0 })?;
+        let len = u16::from_le_bytes([len[0], len[1]]) as usize;
+        if len > N {
+            return Err(Error::InvalidEncoding { depth: 0 });
+        }
+        let bytes = buf.get(2..2 + len).ok_or(Error::BufferTooShort { depth: 0 })?;
+        let s = core::str::from_utf8(bytes).map_err(|_| Error::InvalidEncoding { depth: 0 })?;
+        Ok((StringBuf::new(s).unwrap(), 2 + len))
+    }
+}
+
+impl<const N: usize> Default for StringBuf<N> {
+    fn default() -> Self {
+        StringBuf { len: 0, buf: [0; N], overflow: false }
+    }
+}
+
+impl<const N: usize> Debug for StringBuf<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Display for StringBuf<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, " = {:?}", self.as_str())
+    }
+}
+
+impl<const N: usize> Encode for StringBuf<N> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        if self.overflow {
+            return Err(EncodeError::Other("string longer than the buffer"));
+        }
+        self.as_str().encode(encoder)
+    }
+}
+
+impl<Context, const N: usize> Decode<Context> for StringBuf<N> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = usize::decode(decoder)?;
+        if len > N {
+            return Err(DecodeError::ArrayLengthMismatch { required: N, found: len });
+        }
+        let mut buf = [0; N];
+        decoder.claim_bytes_read(len)?;
+        decoder.reader().read(&mut buf[..len])?;
+        core::str::from_utf8(&buf[..len]).map_err(|inner| DecodeError::Utf8 { inner })?;
+        Ok(StringBuf { len, buf, overflow: false })
+    }
+}
+
+impl<'de, Context, const N: usize> BorrowDecode<'de, Context> for StringBuf<N> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Decode::decode(decoder)
+    }
+}
+
+impl<const N: usize> Value for StringBuf<N> {
+    fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
+        if self.overflow {
+            return None;
+        }
+        let len = u16::try_from(self.len).ok()?;
+        x.get_mut(..2)?.copy_from_slice(&len.to_le_bytes());
+        x.get_mut(2..2 + self.len)?.copy_from_slice(self.as_str().as_bytes());
+        Some(2 + self.len)
+    }
+
+    fn dehydrated_len(&self) -> usize {
+        2 + self.len
+    }
+
+    /// Returns the string as a `StringBuf<N>`, or as a `heapless::String<N>` or `String` when
+    /// the respective features are enabled.
+    fn parse_value<T: 'static>(self, x: &str) -> Option<T> {
+        if !x.is_empty() {
+            return None;
+        }
+        #[cfg(feature = "heapless")]
+        if let Some(s) = downcast(heapless::String::<N>::try_from(self.as_str()).unwrap()) {
+            return Some(s);
+        }
+        #[cfg(feature = "alloc")]
+        if let Some(s) = downcast(alloc::string::String::from(self.as_str())) {
+            return Some(s);
+        }
+        downcast(self)
+    }
+}
+
+impl<const N: usize> Setter for StringBuf<N> {
+    /// Accepts a `StringBuf<N>` or a `&'static str`, and a `heapless::String` or `String` when
+    /// the respective features are enabled, as long as the string fits in `N` bytes.
+    fn parse_setter<T: 'static>(&self, x: &str, set: T) -> Option<Self> {
+        if !x.is_empty() {
+            return None;
+        }
+        let mut set = Some(set);
+        let set = &mut set as &mut dyn core::any::Any;
+        if let Some(s) = set.downcast_mut::<Option<&'static str>>() {
+            return StringBuf::new(s.take()?);
+        }
+        #[cfg(feature = "heapless")]
+        if let Some(s) = set.downcast_mut::<Option<heapless::String<N>>>() {
+            return StringBuf::new(&s.take()?);
+        }
+        #[cfg(feature = "alloc")]
+        if let Some(s) = set.downcast_mut::<Option<alloc::string::String>>() {
+            return StringBuf::new(&s.take()?);
+        }
+        set.downcast_mut::<Option<Self>>()?.take()
+    }
+
+    fn parse_setter_numeric(&self, _x: &str, _set: f64) -> Option<Self> {
+        None
+    }
+
+    /// Converts to the same types as `Value::parse_value`.
+    fn into_value<T: 'static>(self) -> Option<T> {
+        Value::parse_value(self, "")
+    }
+}
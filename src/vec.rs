@@ -0,0 +1,327 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::ops::Index;
+use bincode::{Encode, Decode};
+use crate::{downcast, parse_index, push_id, split_index, Error, FieldMetadata, FieldPath, FieldsType, Getter, NullGetter, RemoteGet, RemoteSet, ResetSegment, Setter, Value};
+
+/// Capacity used for sequences without a fixed capacity (e.g. `Vec<T>`).
+pub(crate) const UNBOUNDED: usize = usize::MAX;
+
+/// `RemoteGet::get` of a sequence holding `elems`.
+pub(crate) fn get<T: RemoteGet, const N: usize>(elems: &[T], x: VecGetter<T::GetterType, N>) -> Result<VecValue<T::ValueType, N>, Error> {
+    match x {
+        VecGetter::Index(idx, g) => {
+            let v = elems.get(idx).ok_or(Error::IndexOutOfBounds { depth: 0 })?;
+            v.get(g).map(|v| VecValue::Index(idx, v)).map_err(Error::nested)
+        }
+        VecGetter::Len => Ok(VecValue::Len(elems.len())),
+        VecGetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+    }
+}
+
+/// `RemoteGet::hydrate` of a sequence of `T` with capacity `N`.
+pub(crate) fn hydrate<T: RemoteGet, const N: usize>(x: VecGetter<T::GetterType, N>, buf: &[u8]) -> Result<(VecValue<T::ValueType, N>, usize), Error> {
+    match x {
+        VecGetter::Index(idx, g) => {
+            if idx >= N {
+                return Err(Error::IndexOutOfBounds { depth: 0 });
+            }
+            T::hydrate(g, buf).map(|(v, len)| (VecValue::Index(idx, v), len)).map_err(Error::nested)
+        }
+        VecGetter::Len => usize::hydrate(NullGetter, buf).map(|(v, len)| (VecValue::Len(v), len)).map_err(Error::nested),
+        VecGetter::Empty => Err(Error::EmptyPath { depth: 0 }),
+    }
+}
+
+/// `RemoteSet::diff` of two sequences, which are compared elementwise if they have the same length.
+pub(crate) fn diff<T: RemoteSet>(elems: &[T], other: &[T], path: &FieldPath, changed: &mut dyn FnMut(&FieldPath)) {
+    if elems.len() != other.len() {
+        return changed(path);
+    }
+    for (idx, (v, other)) in elems.iter().zip(other.iter()).enumerate() {
+        v.diff(other, &path.index(idx), changed);
+    }
+}
+
+/// `RemoteGet::dynamic_fields` of a sequence holding `elems`.
+pub(crate) fn dynamic_fields<T: RemoteGet>(elems: &[T], x: &str, keys: &mut dyn FnMut(&str)) -> Option<FieldsType> {
+    match x {
+        "" => Some(FieldsType::Arr(elems.len())),
+        ".len" => Some(FieldsType::Terminal),
+        x => {
+            let (idx, rest) = parse_index(x, elems.len())?;
+            elems[idx].dynamic_fields(rest, keys)
+        }
+    }
+}
+
+/// `RemoteGet::get_metadata` of a sequence of `T` with capacity `N`.
+pub(crate) fn get_metadata<T: RemoteGet, const N: usize>(x: &str) -> Option<FieldMetadata> {
+    match x {
+        "" => Some(FieldMetadata::default()),
+        ".len" => Some(FieldMetadata { type_name: "usize", ..FieldMetadata::default() }),
+        x => T::get_metadata(parse_index(x, N)?.1),
+    }
+}
+
+/// The length `x` given as a number, or `None` if it isn't a non-negative integer.
+fn length(x: f64) -> Option<usize> {
+    let len = x as usize;
+    (x >= 0.0 && len as f64 == x).then_some(len)
+}
+
+/// `set` of `VecSetter::Truncate` on a sequence, see `VecSetter`.
+pub(crate) fn truncate_len(len: Option<usize>) -> Result<usize, Error> {
+    len.ok_or(Error::OutOfRange { depth: 0 })
+}
+
+/// Getter for a variable length sequence with capacity `N`, addressing either an element with
+/// `[idx]` or the current length with `.len`.
+#[derive(Debug, Encode, Decode, Default, Clone, Hash, PartialEq, Eq, Copy)]
+pub enum VecGetter<G, const N: usize> {
+    Index(usize, G),
+    Len,
+    #[default]
+    Empty,
+}
+
+/// Value for a variable length sequence, see `VecGetter`.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Copy)]
+pub enum VecValue<V, const N: usize> {
+    Index(usize, V),
+    Len(usize),
+}
+
+/// Setter for a variable length sequence with capacity `N` (`usize::MAX` for `Vec<T>`).
+///
+/// `Push` appends `T::default()` with the inner setter applied to it, so
+/// `setter!(Config.samples.push = 5)` appends `5`. Pushing to a full sequence (of a fixed
+/// capacity) and popping from an empty one return `Error::IndexOutOfBounds`. `Truncate` carries
+/// `None` if the length was given as a number which isn't a non-negative integer (e.g. `-1.0`),
+/// which `set` rejects with `Error::OutOfRange`.
+#[derive(Debug, Encode, Decode, Default, Clone, Hash, PartialEq, Eq, Copy)]
+pub enum VecSetter<S, const N: usize> {
+    Index(usize, S),
+    Push(S),
+    Pop,
+    Truncate(Option<usize>),
+    #[default]
+    Empty,
+}
+
+impl<G: Getter, const N: usize> Display for VecGetter<G, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VecGetter::Index(idx, g) => write!(f, "[{}]{}", idx, g),
+            VecGetter::Len => write!(f, ".len"),
+            VecGetter::Empty => Ok(()),
+        }
+    }
+}
+
+impl<G: Getter, const N: usize> Getter for VecGetter<G, N> {
+    fn parse_getter(x: &str) -> Option<Self> {
+        if x == ".len" {
+            return Some(VecGetter::Len);
+        }
+        let (idx, rest) = parse_index(x, N)?;
+        Some(VecGetter::Index(idx, G::parse_getter(rest)?))
+    }
+
+    fn get_fields(x: &str) -> Option<FieldsType> {
+        match x {
+            "" if N == UNBOUNDED => Some(FieldsType::Vec),
+            "" => Some(FieldsType::Arr(N)),
+            ".len" => Some(FieldsType::Terminal),
+            x => {
+                let (_, rest) = parse_index(x, N)?;
+                G::get_fields(rest)
+            }
+        }
+    }
+
+    // only elements have a numeric path, addressed by their index
+    fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+        match self {
+            VecGetter::Index(idx, g) => push_id(ids, u32::try_from(*idx).ok()?, |ids| g.to_ids(ids)),
+            _ => None,
+        }
+    }
+
+    fn parse_getter_ids(ids: &[u32]) -> Option<Self> {
+        let (idx, rest) = split_index(ids, N)?;
+        Some(VecGetter::Index(idx, G::parse_getter_ids(rest)?))
+    }
+
+    fn reset_setter<S: Setter>(&self) -> Option<S> {
+        match self {
+            VecGetter::Index(idx, g) => S::reset_in(ResetSegment::Index(*idx), g),
+            VecGetter::Len => None,
+            VecGetter::Empty => S::reset_whole(),
+        }
+    }
+}
+
+impl<G: Getter, const N: usize> VecGetter<G, N> {
+    pub fn arr_get<F>(self, idx: usize, func: F) -> Self where F: Fn(G) -> G {
+        VecGetter::Index(idx, func(G::default()))
+    }
+
+    pub fn make_len<F>(&self, _func: F) -> Self where F: Fn(NullGetter) -> NullGetter {
+        VecGetter::Len
+    }
+}
+
+impl<V: Value, const N: usize> VecValue<V, N> {
+    /// Returns the length, panics if this is not the result of a `.len` getter.
+    pub fn len(self) -> usize {
+        match self {
+            VecValue::Len(len) => len,
+            _ => panic!("VecValue::len called on an element value"),
+        }
+    }
+
+    /// Whether the length read with `.len` is zero, panics like `len`.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<V: Value, const N: usize> Index<usize> for VecValue<V, N> {
+    type Output = V;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self {
+            VecValue::Index(idx, v) => {
+                assert_eq!(index, *idx);
+                v
+            }
+            _ => panic!("VecValue indexed on a length value"),
+        }
+    }
+}
+
+impl<V: Value, const N: usize> Value for VecValue<V, N> {
+    fn dehydrate(&self, x: &mut [u8]) -> Option<usize> {
+        match self {
+            VecValue::Index(_, v) => v.dehydrate(x),
+            VecValue::Len(len) => len.dehydrate(x),
+        }
+    }
+
+    fn dehydrated_len(&self) -> usize {
+        match self {
+            VecValue::Index(_, v) => v.dehydrated_len(),
+            VecValue::Len(len) => len.dehydrated_len(),
+        }
+    }
+
+    fn as_float(&self) -> Option<f32> {
+        match self {
+            VecValue::Index(_, v) => v.as_float(),
+            VecValue::Len(len) => len.as_float(),
+        }
+    }
+
+    fn parse_value<T: 'static>(self, x: &str) -> Option<T> {
+        match self {
+            VecValue::Index(idx, v) => {
+                let (x_idx, rest) = parse_index(x, N)?;
+                if x_idx == idx {
+                    v.parse_value(rest)
+                } else {
+                    None
+                }
+            }
+            VecValue::Len(len) => len.parse_value(x.strip_prefix(".len")?),
+        }
+    }
+}
+
+impl<S: Setter, const N: usize> Display for VecSetter<S, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VecSetter::Index(idx, s) => write!(f, "[{}]{}", idx, s),
+            VecSetter::Push(s) => write!(f, ".push{}", s),
+            VecSetter::Pop => write!(f, ".pop"),
+            VecSetter::Truncate(Some(len)) => write!(f, ".truncate = {}", len),
+            // parsed back by `parse_setter_numeric`
+            VecSetter::Truncate(None) => write!(f, ".truncate = NaN"),
+            VecSetter::Empty => Ok(()),
+        }
+    }
+}
+
+impl<S: Setter, const N: usize> Setter for VecSetter<S, N> {
+    fn parse_setter<T: 'static>(&self, x: &str, set: T) -> Option<Self> {
+        match x {
+            ".pop" => {
+                downcast::<T, ()>(set)?;
+                Some(VecSetter::Pop)
+            }
+            ".truncate" => Some(VecSetter::Truncate(Some(downcast(set)?))),
+            x => match x.strip_prefix(".push") {
+                Some(rest) => Some(VecSetter::Push(S::default().parse_setter(rest, set)?)),
+                None => {
+                    let (idx, rest) = parse_index(x, N)?;
+                    Some(VecSetter::Index(idx, S::default().parse_setter(rest, set)?))
+                }
+            },
+        }
+    }
+
+    fn parse_setter_numeric(&self, x: &str, set: f64) -> Option<Self> {
+        match x {
+            ".truncate" => Some(VecSetter::Truncate(length(set))),
+            x => match x.strip_prefix(".push") {
+                Some(rest) => Some(VecSetter::Push(S::default().parse_setter_numeric(rest, set)?)),
+                None => {
+                    let (idx, rest) = parse_index(x, N)?;
+                    Some(VecSetter::Index(idx, S::default().parse_setter_numeric(rest, set)?))
+                }
+            },
+        }
+    }
+
+    fn to_ids(&self, ids: &mut [u32]) -> Option<usize> {
+        match self {
+            VecSetter::Index(idx, s) => push_id(ids, u32::try_from(*idx).ok()?, |ids| s.to_ids(ids)),
+            _ => None,
+        }
+    }
+
+    fn parse_setter_ids<T: 'static>(&self, ids: &[u32], set: T) -> Option<Self> {
+        let (idx, rest) = split_index(ids, N)?;
+        Some(VecSetter::Index(idx, S::default().parse_setter_ids(rest, set)?))
+    }
+
+    fn parse_setter_ids_numeric(&self, ids: &[u32], set: f64) -> Option<Self> {
+        let (idx, rest) = split_index(ids, N)?;
+        Some(VecSetter::Index(idx, S::default().parse_setter_ids_numeric(rest, set)?))
+    }
+
+    fn reset_in<G: Getter>(segment: ResetSegment, inner: &G) -> Option<Self> {
+        match segment {
+            ResetSegment::Index(idx) => Some(VecSetter::Index(idx, inner.reset_setter()?)),
+            _ => None,
+        }
+    }
+}
+
+impl<S: Setter, const N: usize> VecSetter<S, N> {
+    pub fn arr_set<F>(self, idx: usize, func: F) -> Self where F: Fn(S) -> S {
+        VecSetter::Index(idx, func(S::default()))
+    }
+
+    pub fn make_push<F>(&self, func: F) -> Self where F: Fn(S) -> S {
+        VecSetter::Push(func(S::default()))
+    }
+
+    pub fn make_pop<F>(&self, _func: F) -> Self where F: Fn(()) {
+        VecSetter::Pop
+    }
+
+    pub fn make_truncate<F>(&self, func: F) -> Self where F: Fn(usize) -> usize {
+        VecSetter::Truncate(Some(func(0)))
+    }
+}
@@ -0,0 +1,144 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Gains {
+    #[remote(alias = "p")]
+    kp: f32,
+    #[remote(deprecated)]
+    ki: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Mode {
+    #[remote(alias = "Off")]
+    Idle,
+    #[remote(alias = "Pid", alias = "Closed")]
+    Run(Gains),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Config {
+    #[remote(rename = "voltage_limit", alias = "max_voltage", unit = "V")]
+    limit: f32,
+    #[remote(alias = "pid")]
+    gains: Gains,
+    mode: Mode,
+}
+
+/// an alias which extends the path of another field
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Filter {
+    kp: f32,
+    #[remote(alias = "kpx")]
+    kp_extra: f32,
+}
+
+#[test]
+fn alias_get() {
+    let c = Config {
+        limit: 12.0,
+        gains: Gains { kp: 1.0, ki: 0.5 },
+        mode: Mode::Run(Gains { kp: 2.0, ki: 0.0 }),
+    };
+
+    let getter = <Config as RemoteGet>::GetterType::parse_getter(".max_voltage").unwrap();
+    assert!(getter == getter!(Config.limit));
+    assert_eq!(format!("{}", getter), ".voltage_limit");
+    let value = c.get(getter).unwrap();
+    assert_eq!(value.parse_value::<f32>(".max_voltage"), Some(12.0));
+    assert_eq!(value.parse_value::<f32>(".voltage_limit"), Some(12.0));
+
+    let getter = <Config as RemoteGet>::GetterType::parse_getter(".pid.p").unwrap();
+    assert!(getter == getter!(Config.gains.kp));
+    assert_eq!(format!("{}", getter), ".gains.kp");
+    assert_eq!(c.get(getter).unwrap().gains().kp(), 1.0);
+
+    let getter = <Config as RemoteGet>::GetterType::parse_getter(".mode::Closed.kp").unwrap();
+    assert!(getter == getter!(Config.mode::Run.kp));
+    assert_eq!(format!("{}", getter), ".mode::Run.kp");
+    assert!(<Config as RemoteGet>::GetterType::parse_getter(".limit").is_none());
+}
+
+#[test]
+fn alias_fields() {
+    assert_eq!(
+        Some(FieldsType::Fields(&[".voltage_limit", ".gains", ".mode"])),
+        <Config as RemoteGet>::GetterType::get_fields("")
+    );
+    assert_eq!(
+        Some(FieldsType::Fields(&[".kp", ".ki"])),
+        <Config as RemoteGet>::GetterType::get_fields(".pid")
+    );
+    assert_eq!(Some(FieldsType::Terminal), <Config as RemoteGet>::GetterType::get_fields(".pid.p"));
+    assert_eq!(
+        Some(FieldsType::Fields(&[".kp", ".ki"])),
+        <Mode as RemoteGet>::GetterType::get_fields("::Pid")
+    );
+
+    let c = Config {
+        limit: 12.0,
+        gains: Gains { kp: 1.0, ki: 0.5 },
+        mode: Mode::Run(Gains { kp: 2.0, ki: 0.0 }),
+    };
+    assert_eq!(Some(FieldsType::Terminal), c.dynamic_fields(".mode::Closed.p", &mut |_| {}));
+    assert_eq!(None, c.mode.dynamic_fields("::Idle", &mut |_| {}));
+}
+
+#[test]
+fn alias_metadata() {
+    let canonical = Config::get_metadata(".voltage_limit").unwrap();
+    assert_eq!(Some("V"), canonical.unit);
+    assert!(!canonical.deprecated);
+    let alias = Config::get_metadata(".max_voltage").unwrap();
+    assert_eq!(Some("V"), alias.unit);
+    assert!(alias.deprecated);
+
+    assert!(!Config::get_metadata(".gains.kp").unwrap().deprecated);
+    assert!(Config::get_metadata(".gains.p").unwrap().deprecated);
+    assert!(Config::get_metadata(".pid.kp").unwrap().deprecated);
+    assert!(Config::get_metadata(".gains.ki").unwrap().deprecated);
+
+    assert!(!Mode::get_metadata("::Idle").unwrap().deprecated);
+    assert!(Mode::get_metadata("::Off").unwrap().deprecated);
+    assert!(Mode::get_metadata("::Pid").unwrap().deprecated);
+    assert!(Mode::get_metadata("::Pid.kp").unwrap().deprecated);
+    assert!(!Mode::get_metadata("::Run.kp").unwrap().deprecated);
+}
+
+#[test]
+fn alias_set() {
+    let mut c = Config {
+        limit: 12.0,
+        gains: Gains { kp: 1.0, ki: 0.5 },
+        mode: Mode::Run(Gains { kp: 2.0, ki: 0.0 }),
+    };
+    c.set(Config::dynamic_setter(".max_voltage", 24.0f32).unwrap()).unwrap();
+    c.set(Config::dynamic_setter_numeric(".pid.p", 3.0).unwrap()).unwrap();
+    c.set(Config::dynamic_setter(".mode::Pid.p", 4.0f32).unwrap()).unwrap();
+    assert_eq!(c.limit, 24.0);
+    assert_eq!(c.gains.kp, 3.0);
+    assert!(matches!(c.mode, Mode::Run(Gains { kp, .. }) if kp == 4.0));
+
+    let setter = Config::dynamic_setter(".mode::Off", ()).unwrap();
+    assert_eq!(format!("{}", setter), ".mode = ::Idle");
+    c.set(setter).unwrap();
+    assert!(matches!(c.mode, Mode::Idle));
+
+    c.set(Config::dynamic_setter(".max_voltage reset", ()).unwrap()).unwrap();
+    assert_eq!(c.limit, 0.0);
+    assert_eq!(format!("{}", Config::dynamic_setter(".pid.p", 1.0f32).unwrap()), ".gains.kp1");
+    assert!(Config::dynamic_setter(".limit", 1.0f32).is_none());
+}
+
+#[test]
+fn alias_prefix() {
+    let mut filter = Filter { kp: 1.0, kp_extra: 2.0 };
+    let getter = <Filter as RemoteGet>::GetterType::parse_getter(".kpx").unwrap();
+    assert!(getter == getter!(Filter.kp_extra));
+    assert_eq!(filter.get(getter).unwrap().kp_extra(), 2.0);
+    assert!(Filter::get_metadata(".kpx").unwrap().deprecated);
+
+    filter.set(Filter::dynamic_setter(".kpx", 3.0f32).unwrap()).unwrap();
+    filter.set(Filter::dynamic_setter(".kp", 4.0f32).unwrap()).unwrap();
+    assert_eq!((filter.kp, filter.kp_extra), (4.0, 3.0));
+}
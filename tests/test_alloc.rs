@@ -0,0 +1,251 @@
+#![cfg(feature = "alloc")]
+
+use std::collections::BTreeMap;
+use remote_obj::prelude::*;
+use remote_obj::{StringBuf, STRING_LEN};
+
+#[derive(RemoteSetter, RemoteGetter, Default, PartialEq, Debug)]
+pub struct Limits {
+    min: i16,
+    max: i16,
+}
+
+#[derive(RemoteSetter, RemoteGetter, Default, PartialEq, Debug)]
+pub enum Source {
+    #[default]
+    Off,
+    Sweep(Vec<u16>),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Config {
+    samples: Vec<u16>,
+    name: String,
+    limits: BTreeMap<String, Limits>,
+    ranges: BTreeMap<u128, Limits>,
+    gains: BTreeMap<u8, f32>,
+    inner: Box<Limits>,
+    source: Source,
+}
+
+const WIDE_KEY: u128 = 1 << 100;
+
+#[test]
+fn test_vec() {
+    let mut c = Config {
+        samples: vec![1, 2],
+        name: "motor".into(),
+        limits: BTreeMap::from([
+            ("motor1".into(), Limits { min: -5, max: 5 }),
+            ("fan \"a\\b\"\n".into(), Limits { min: 0, max: 1 }),
+        ]),
+        ranges: BTreeMap::from([(WIDE_KEY, Limits { min: 1, max: 2 })]),
+        gains: BTreeMap::from([(3, 0.5)]),
+        inner: Box::new(Limits { min: 0, max: 1 }),
+        source: Source::Sweep(vec![10, 20, 30]),
+    };
+
+    c.set(setter!(Config.samples[1] = 5)).unwrap();
+    let v = c.get(getter!(Config.samples[1])).unwrap();
+    assert_eq!(v.samples()[1], 5);
+    assert_eq!(c.get(getter!(Config.samples[2])).err(), Some(Error::IndexOutOfBounds { depth: 1 }));
+
+    for i in 0..10 {
+        c.set(setter!(Config.samples.push = i)).unwrap();
+    }
+    assert_eq!(c.get(getter!(Config.samples.len)).unwrap().samples().len(), 12);
+    c.set(setter!(Config.samples.truncate = 1)).unwrap();
+    c.set(setter!(Config.samples.pop)).unwrap();
+    assert_eq!(c.set(setter!(Config.samples.pop)), Err(Error::IndexOutOfBounds { depth: 1 }));
+
+    // a `Vec` without capacity grows
+    c.samples = Vec::new();
+    c.set(Config::dynamic_setter::<u16>(".samples.push", 7).unwrap()).unwrap();
+    let path = ".samples[0]";
+    let getter = Config::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = c.get(getter).unwrap();
+    assert_eq!(value.parse_value::<u16>(path), Some(7));
+    let mut buf = [0; 8];
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Config as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+}
+
+#[test]
+fn test_string() {
+    let mut c = Config {
+        samples: vec![1, 2],
+        name: "motor".into(),
+        limits: BTreeMap::from([
+            ("motor1".into(), Limits { min: -5, max: 5 }),
+            ("fan \"a\\b\"\n".into(), Limits { min: 0, max: 1 }),
+        ]),
+        ranges: BTreeMap::from([(WIDE_KEY, Limits { min: 1, max: 2 })]),
+        gains: BTreeMap::from([(3, 0.5)]),
+        inner: Box::new(Limits { min: 0, max: 1 }),
+        source: Source::Sweep(vec![10, 20, 30]),
+    };
+
+    c.set(setter!(Config.name = String::from("pump"))).unwrap();
+    assert_eq!(c.name, "pump");
+    c.set(Config::dynamic_setter(".name", "fan").unwrap()).unwrap();
+    assert_eq!(c.name, "fan");
+
+    let getter = getter!(Config.name);
+    let value = c.get(getter).unwrap();
+    assert_eq!(value.parse_value::<String>(".name").unwrap(), "fan");
+    let mut buf = [0; 8];
+    assert_eq!(value.dehydrate(&mut buf), Some(5));
+    let (rehydrated_v, _) = <Config as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert!(rehydrated_v == value);
+
+    // too long to be transferred
+    c.name = "x".repeat(STRING_LEN + 1);
+    assert_eq!(c.get(getter).err(), Some(Error::CapacityExceeded { depth: 1 }));
+    assert!(Config::dynamic_setter(".name", c.name.clone()).is_none());
+    let setter = setter!(Config.name = "y".repeat(STRING_LEN + 1));
+    assert_eq!(c.set(setter), Err(Error::CapacityExceeded { depth: 1 }));
+    assert_eq!(c.name, "x".repeat(STRING_LEN + 1));
+    let name = StringBuf::<STRING_LEN>::setter_from("y".repeat(STRING_LEN + 1));
+    assert!(name.is_overflow());
+    assert!(bincode::encode_into_slice(name, &mut [0; 128], bincode::config::standard()).is_err());
+}
+
+#[test]
+fn test_map() {
+    let mut c = Config {
+        samples: vec![1, 2],
+        name: "motor".into(),
+        limits: BTreeMap::from([
+            ("motor1".into(), Limits { min: -5, max: 5 }),
+            ("fan \"a\\b\"\n".into(), Limits { min: 0, max: 1 }),
+        ]),
+        ranges: BTreeMap::from([(WIDE_KEY, Limits { min: 1, max: 2 })]),
+        gains: BTreeMap::from([(3, 0.5)]),
+        inner: Box::new(Limits { min: 0, max: 1 }),
+        source: Source::Sweep(vec![10, 20, 30]),
+    };
+
+    c.set(setter!(Config.limits["motor1"].max = 7)).unwrap();
+    assert_eq!(c.limits["motor1"], Limits { min: -5, max: 7 });
+    let v = c.get(getter!(Config.limits["motor1"].max)).unwrap();
+    assert_eq!(v.limits()["motor1"].max(), 7);
+    c.set(setter!(Config.gains[3] = 1.5)).unwrap();
+    assert_eq!(c.gains[&3], 1.5);
+    assert_eq!(c.get(getter!(Config.limits.len)).unwrap().limits().len(), 2);
+
+    // keys can't be inserted remotely
+    assert_eq!(c.set(setter!(Config.limits["motor2"].max = 7)), Err(Error::KeyNotFound { depth: 1 }));
+    assert_eq!(c.get(getter!(Config.gains[4])).err(), Some(Error::KeyNotFound { depth: 1 }));
+
+    c.set(Config::dynamic_setter_numeric(".limits[\"motor1\"].min", -3.0).unwrap()).unwrap();
+    assert_eq!(c.limits["motor1"].min, -3);
+
+    let path = ".limits[\"motor1\"].min";
+    let getter = Config::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = c.get(getter).unwrap();
+    assert_eq!(value.parse_value::<i16>(path), Some(-3));
+    let mut buf = [0; 8];
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Config as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+
+    assert!(Config::dynamic_getter(".limits[\"motor1\"].mid").is_none());
+    assert!(Config::dynamic_getter(".limits[motor1].min").is_some());
+    assert_eq!(c.get(Config::dynamic_getter(".limits[motor1].min").unwrap()).err(), Some(Error::KeyNotFound { depth: 1 }));
+
+    // keys are escaped like `Debug`
+    let key = "fan \"a\\b\"\n";
+    let path = ".limits[\"fan \\\"a\\\\b\\\"\\n\"].max";
+    let getter = Config::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    assert_eq!(format!("{}", getter!(Config.limits[key].max)), path);
+    c.set(Config::dynamic_setter_numeric(path, 4.0).unwrap()).unwrap();
+    assert_eq!(c.get(getter).unwrap().limits()[key].max(), 4);
+
+    // other spellings of a key give the value under the key `dynamic_fields` reports
+    let path = ".limits[\"\\u{6d}otor1\"].max";
+    let getter = Config::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = c.get(getter).unwrap();
+    assert_eq!(value.limits()["motor1"].max(), 7);
+    assert_eq!(value.parse_value::<i16>(".limits[\"motor1\"].max"), Some(7));
+    let mut buf = [0; 8];
+    let len = value.dehydrate(&mut buf).unwrap();
+    assert!(<Config as RemoteGet>::hydrate(getter, &buf[..len]).unwrap().0 == value);
+    c.set(setter!(Config.ranges[WIDE_KEY].min = 3)).unwrap();
+    assert_eq!(c.ranges[&WIDE_KEY].min, 3);
+
+    // keys too long to be transferred
+    let long = "motor".repeat(8);
+    c.limits.insert(long.clone(), Limits::default());
+    assert_eq!(c.set(setter!(Config.limits[long.as_str()].max = 7)), Err(Error::CapacityExceeded { depth: 1 }));
+    assert_eq!(c.get(getter!(Config.limits[long.as_str()].max)).err(), Some(Error::CapacityExceeded { depth: 1 }));
+    let v = c.get(getter!(Config.limits["motor1"].max)).unwrap();
+    assert!(v.limits().get(long.as_str()).is_none());
+    assert_eq!(v.limits().get("motor1").map(|v| v.max()), Some(7));
+
+    c.reset(getter!(Config.limits["motor1"].max)).unwrap();
+    assert_eq!(c.limits["motor1"], Limits { min: -3, max: 0 });
+    assert_eq!(c.reset(getter!(Config.limits.len)), Err(Error::NotResettable { depth: 0 }));
+}
+
+#[test]
+fn test_box() {
+    let mut c = Config {
+        samples: vec![1, 2],
+        name: "motor".into(),
+        limits: BTreeMap::from([
+            ("motor1".into(), Limits { min: -5, max: 5 }),
+            ("fan \"a\\b\"\n".into(), Limits { min: 0, max: 1 }),
+        ]),
+        ranges: BTreeMap::from([(WIDE_KEY, Limits { min: 1, max: 2 })]),
+        gains: BTreeMap::from([(3, 0.5)]),
+        inner: Box::new(Limits { min: 0, max: 1 }),
+        source: Source::Sweep(vec![10, 20, 30]),
+    };
+
+    c.set(setter!(Config.inner.max = 3)).unwrap();
+    assert_eq!(*c.inner, Limits { min: 0, max: 3 });
+    assert_eq!(c.get(getter!(Config.inner.max)).unwrap().inner().max(), 3);
+    assert_eq!(Some(FieldsType::Fields(&[".min", ".max"])), <Config as RemoteGet>::GetterType::get_fields(".inner"));
+}
+
+#[test]
+fn test_fields() {
+    let c = Config {
+        samples: vec![1, 2],
+        name: "motor".into(),
+        limits: BTreeMap::from([
+            ("motor1".into(), Limits { min: -5, max: 5 }),
+            ("fan \"a\\b\"\n".into(), Limits { min: 0, max: 1 }),
+        ]),
+        ranges: BTreeMap::from([(WIDE_KEY, Limits { min: 1, max: 2 })]),
+        gains: BTreeMap::from([(3, 0.5)]),
+        inner: Box::new(Limits { min: 0, max: 1 }),
+        source: Source::Sweep(vec![10, 20, 30]),
+    };
+    let getter_fields = <Config as RemoteGet>::GetterType::get_fields;
+
+    assert_eq!(Some(FieldsType::Vec), getter_fields(".samples"));
+    assert_eq!(Some(FieldsType::Map), getter_fields(".limits"));
+    assert_eq!(Some(FieldsType::Fields(&[".min", ".max"])), getter_fields(".limits[\"any\"]"));
+
+    let mut keys = Vec::new();
+    let mut collect = |k: &str| keys.push(k.to_string());
+    assert_eq!(Some(FieldsType::Arr(2)), c.dynamic_fields(".samples", &mut collect));
+    assert_eq!(Some(FieldsType::Map), c.dynamic_fields(".limits", &mut collect));
+    assert_eq!(Some(FieldsType::Map), c.dynamic_fields(".gains", &mut collect));
+    assert_eq!(keys, ["\"fan \\\"a\\\\b\\\"\\n\"", "\"motor1\"", "3"]);
+
+    let mut ignore = |_: &str| {};
+    assert_eq!(Some(FieldsType::Fields(&[".min", ".max"])), c.dynamic_fields(".limits[\"motor1\"]", &mut ignore));
+    assert_eq!(None, c.dynamic_fields(".limits[\"motor2\"]", &mut ignore));
+    assert_eq!(Some(FieldsType::Arr(3)), c.dynamic_fields(".source::Sweep", &mut ignore));
+    assert_eq!(Some(FieldsType::Terminal), c.dynamic_fields(".samples[1]", &mut ignore));
+    assert_eq!(getter_fields(""), c.dynamic_fields("", &mut ignore));
+}
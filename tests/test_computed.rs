@@ -0,0 +1,61 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter, Clone, Copy)]
+pub struct Stats {
+    min: f32,
+    max: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(computed(name = "power", ty = "f32", with = "Self::power"))]
+#[remote(computed(name = "range", ty = "Stats", with = "Supply::range"))]
+pub struct Supply {
+    voltage: f32,
+    current: f32,
+}
+
+impl Supply {
+    fn power(&self) -> f32 {
+        self.voltage * self.current
+    }
+
+    fn range(&self) -> Stats {
+        Stats { min: self.voltage.min(self.current), max: self.voltage.max(self.current) }
+    }
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Rack {
+    supply: Supply,
+}
+
+#[test]
+fn test_computed() {
+    let mut rack = Rack { supply: Supply { voltage: 12.0, current: 2.0 } };
+
+    let v = rack.get(getter!(Rack.supply.power)).unwrap();
+    assert_eq!(v.supply().power(), 24.0);
+    rack.set(setter!(Rack.supply.current = 0.5)).unwrap();
+    let v = rack.get(getter!(Rack.supply.range.min)).unwrap();
+    assert_eq!(v.supply().range().min(), 0.5);
+
+    // read-only
+    assert!(Rack::dynamic_setter_numeric(".supply.power", 1.0).is_none());
+
+    let path = ".supply.power";
+    let getter = Rack::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = rack.get(getter).unwrap();
+    assert_eq!(value.parse_value::<f32>(path), Some(6.0));
+    assert_eq!(value.as_float(), Some(6.0));
+    let mut buf = [0; 4];
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Rack as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+
+    let getter_fields = <Rack as RemoteGet>::GetterType::get_fields;
+    assert_eq!(Some(FieldsType::Fields(&[".voltage", ".current", ".power", ".range"])), getter_fields(".supply"));
+    assert_eq!(Some(FieldsType::Fields(&[".min", ".max"])), getter_fields(".supply.range"));
+    assert_eq!(Some(FieldsType::Terminal), rack.dynamic_fields(".supply.range.max", &mut |_| {}));
+}
@@ -91,4 +91,31 @@ fn test_dynamic() {
     assert_eq!(Some(FieldsType::Fields(&[".a", ".b"])), <Test as RemoteGet>::GetterType::get_fields(".c::B"));
     assert_eq!(Some(FieldsType::Terminal), <Test as RemoteGet>::GetterType::get_fields(".c::B.a"));
     assert_eq!(Some(FieldsType::Arr(8)), <Test as RemoteGet>::GetterType::get_fields(".d"));
-}
\ No newline at end of file
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Floats {
+    a: f32,
+    b: u32,
+}
+
+#[test]
+fn test_dynamic_type_mismatch() {
+    // same size, different type
+    assert!(Test::dynamic_setter::<u8>(".a", 1).is_none());
+    assert!(Test::dynamic_setter::<u8>(".d[4]", 1).is_none());
+    assert!(Test::dynamic_setter::<i8>(".c::A", 1).is_none());
+    assert!(Floats::dynamic_setter::<u32>(".a", 1).is_none());
+    assert!(Floats::dynamic_setter::<f32>(".b", 1.0).is_none());
+
+    let mut floats = Floats {
+        a: 0.0,
+        b: 0,
+    };
+    floats.set(Floats::dynamic_setter::<f32>(".a", 1.5).unwrap()).unwrap();
+
+    let value = floats.get(Floats::dynamic_getter(".a").unwrap()).unwrap();
+    assert_eq!(value.parse_value::<u32>(".a"), None);
+    assert_eq!(value.parse_value::<i32>(".a"), None);
+    assert_eq!(value.parse_value::<f32>(".a"), Some(1.5));
+}
@@ -0,0 +1,195 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Limits {
+    lo: i16,
+    hi: i16,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Mode {
+    Off,
+    Pid { kp: f32, ki: f32, limits: Limits },
+    Pair(u8, u8),
+    Duty(f32),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Motor {
+    mode: Mode,
+}
+
+#[test]
+fn test_struct_variant() {
+    let mut motor = Motor {
+        mode: Mode::Pid {
+            kp: 1.0,
+            ki: 0.0,
+            limits: Limits { lo: -10, hi: 10 },
+        },
+    };
+
+    motor.set(setter!(Motor.mode::Pid.ki = 0.5)).unwrap();
+    let v = motor.get(getter!(Motor.mode::Pid.ki)).unwrap();
+    assert_eq!(v.mode().Pid().ki(), 0.5);
+
+    motor.set(setter!(Motor.mode::Pid.limits.hi = 20)).unwrap();
+    let v = motor.get(getter!(Motor.mode::Pid.limits.hi)).unwrap();
+    assert_eq!(v.mode().Pid().limits().hi(), 20);
+
+    let path = ".mode::Pid.kp";
+    motor.set(Motor::dynamic_setter::<f32>(path, 2.0).unwrap()).unwrap();
+    motor.set(Motor::dynamic_setter_numeric(".mode::Pid.limits.lo", -20.0).unwrap()).unwrap();
+    let getter = Motor::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = motor.get(getter).unwrap();
+    assert_eq!(value.parse_value::<f32>(path), Some(2.0));
+    assert_eq!(value.as_float(), Some(2.0));
+
+    let mut buf = [0; 4];
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Motor as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+
+    match &motor.mode {
+        Mode::Pid { kp, ki, limits } => {
+            assert_eq!((*kp, *ki, limits.lo, limits.hi), (2.0, 0.5, -20, 20));
+        }
+        _ => unreachable!(),
+    }
+
+    // wrong variant
+    assert_eq!(motor.set(setter!(Motor.mode::Pair.0 = 1)), Err(Error::WrongVariant { depth: 1 }));
+    assert_eq!(motor.get(getter!(Motor.mode::Pair.1)).err(), Some(Error::WrongVariant { depth: 1 }));
+
+    // errors below the variant fields keep their depth
+    let getter = getter!(Motor.mode::Pid.limits.hi);
+    assert_eq!(<Motor as RemoteGet>::hydrate(getter, &[0]).err(), Some(Error::BufferTooShort { depth: 4 }));
+
+    assert_eq!(Some(FieldsType::Fields(&["::Duty", "::Pid", "::Pair", "VARIANT"])), <Motor as RemoteGet>::GetterType::get_fields(".mode"));
+    assert_eq!(Some(FieldsType::Fields(&[".kp", ".ki", ".limits"])), <Motor as RemoteGet>::GetterType::get_fields(".mode::Pid"));
+    assert_eq!(Some(FieldsType::Fields(&[".0", ".1"])), <Motor as RemoteGet>::GetterType::get_fields(".mode::Pair"));
+    assert_eq!(Some(FieldsType::Terminal), <Motor as RemoteGet>::GetterType::get_fields(".mode::Pair.1"));
+}
+
+#[test]
+fn test_tuple_variant() {
+    let mut motor = Motor {
+        mode: Mode::Pair(1, 2),
+    };
+
+    motor.set(setter!(Motor.mode::Pair.1 = 3)).unwrap();
+    let v = motor.get(getter!(Motor.mode::Pair.1)).unwrap();
+    assert_eq!(v.mode().Pair()._1(), 3);
+
+    motor.set(Motor::dynamic_setter::<u8>(".mode::Pair.0", 4).unwrap()).unwrap();
+    match motor.mode {
+        Mode::Pair(a, b) => assert_eq!((a, b), (4, 3)),
+        _ => unreachable!(),
+    }
+
+    assert_eq!(motor.set(setter!(Motor.mode::Pid.kp = 1.0)), Err(Error::WrongVariant { depth: 1 }));
+
+    // the current variant can be read and sent over the wire
+    let g = getter!(Motor.mode.var);
+    let v = motor.get(g).unwrap();
+    assert!(v.mode() == <Mode as RemoteGet>::ValueType::Pair);
+    let mut buf = [0; 1];
+    v.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, _) = <Motor as RemoteGet>::hydrate(g, &buf).unwrap();
+    assert!(rehydrated_v == v);
+
+    motor.set(setter!(Motor.mode::Off)).unwrap();
+    assert!(matches!(motor.mode, Mode::Off));
+}
+
+#[derive(RemoteSetter, RemoteGetter, bincode::Encode, bincode::Decode, Clone, Copy, PartialEq, Debug)]
+#[remote(derive(bincode::Encode, bincode::Decode))]
+pub struct Gains {
+    kp: f32,
+    ki: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter, bincode::Encode, bincode::Decode, Clone, Copy, PartialEq, Debug)]
+#[remote(replace, derive(bincode::Encode, bincode::Decode))]
+pub enum Control {
+    Off,
+    Pid(Gains),
+    Pair(u8, u8),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(derive(bincode::Encode, bincode::Decode))]
+pub struct Drive {
+    control: Control,
+}
+
+#[test]
+fn test_replace() {
+    let mut drive = Drive {
+        control: Control::Off,
+    };
+
+    // inner writes still need the right variant
+    assert!(drive.set(setter!(Drive.control::Pid.kp = 1.0)).is_err());
+
+    drive.set(setter!(Drive.control = Control::Pid(Gains { kp: 1.0, ki: 2.0 }))).unwrap();
+    assert_eq!(drive.control, Control::Pid(Gains { kp: 1.0, ki: 2.0 }));
+    drive.set(setter!(Drive.control::Pid.ki = 3.0)).unwrap();
+    assert_eq!(drive.control, Control::Pid(Gains { kp: 1.0, ki: 3.0 }));
+
+    let setter = Drive::dynamic_setter(".control", Control::Pair(1, 2)).unwrap();
+    assert_eq!(format!("{}", setter), ".control = ::Pair(..)");
+    drive.set(setter).unwrap();
+    assert_eq!(drive.control, Control::Pair(1, 2));
+
+    // the replacing value has to be of the enum type
+    assert!(Drive::dynamic_setter(".control", Gains { kp: 0.0, ki: 0.0 }).is_none());
+
+    // round trip over the wire
+    let config = bincode::config::standard();
+    let mut buf = [0; 32];
+    let setter = setter!(Drive.control = Control::Pid(Gains { kp: 4.0, ki: 5.0 }));
+    let len = bincode::encode_into_slice(setter, &mut buf, config).unwrap();
+    let (decoded, _): (<Drive as RemoteSet>::SetterType, usize) = bincode::decode_from_slice(&buf[..len], config).unwrap();
+    drive.set(decoded).unwrap();
+    assert_eq!(drive.control, Control::Pid(Gains { kp: 4.0, ki: 5.0 }));
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Command {
+    Idle,
+    #[remote(write_only)]
+    Stop,
+    #[remote(write_only)]
+    Jog(f32),
+    #[remote(write_only)]
+    Move { target: i32, speed: u16 },
+    #[remote(read_only)]
+    Fault(u8),
+}
+
+#[test]
+fn test_write_only_variant() {
+    let mut command = Command::Idle;
+
+    // write-only variants can be written, but not read
+    command.set(setter!(Command::Stop)).unwrap();
+    assert!(matches!(command, Command::Stop));
+    command = Command::Jog(0.0);
+    command.set(setter!(Command::Jog = 0.5)).unwrap();
+    assert!(matches!(command, Command::Jog(speed) if speed == 0.5));
+    command = Command::Move { target: 0, speed: 0 };
+    command.set(Command::dynamic_setter("::Move.target", 100i32).unwrap()).unwrap();
+    assert!(matches!(command, Command::Move { target: 100, speed: 0 }));
+
+    assert!(Command::dynamic_getter("::Jog").is_none());
+    assert!(Command::dynamic_getter("::Move.target").is_none());
+    assert_eq!(Some(FieldsType::Fields(&["::Fault", "VARIANT"])), <Command as RemoteGet>::GetterType::get_fields(""));
+
+    // read-only variants are the other way around
+    command = Command::Fault(3);
+    assert_eq!(command.get(getter!(Command::Fault)).unwrap().Fault(), 3);
+    assert!(Command::dynamic_setter("::Fault", 4u8).is_none());
+}
@@ -0,0 +1,53 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Inner {
+    a: i8,
+    b: i8,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum TestEnum {
+    A,
+    B(Inner),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Test {
+    a: i8,
+    b: TestEnum,
+    c: [Inner; 4],
+}
+
+#[test]
+fn test_error() {
+    let mut test = Test {
+        a: 0,
+        b: TestEnum::A,
+        c: [Inner { a: 0, b: 0 }, Inner { a: 0, b: 0 }, Inner { a: 0, b: 0 }, Inner { a: 0, b: 0 }],
+    };
+
+    // wrong variant is reported at the `::B` segment
+    assert_eq!(test.set(setter!(Test.b::B.a = 1)), Err(Error::WrongVariant { depth: 1 }));
+    assert_eq!(test.get(getter!(Test.b::B.a)).err(), Some(Error::WrongVariant { depth: 1 }));
+
+    // out of range index is reported at the `[4]` segment
+    assert_eq!(test.set(setter!(Test.c[4].a = 1)), Err(Error::IndexOutOfBounds { depth: 1 }));
+    assert_eq!(test.get(getter!(Test.c[4].a)).err(), Some(Error::IndexOutOfBounds { depth: 1 }));
+    assert_eq!(<Test as RemoteGet>::hydrate(getter!(Test.c[4].a), &[0]).err(), Some(Error::IndexOutOfBounds { depth: 1 }));
+    assert!(Test::dynamic_getter(".c[4].a").is_none());
+    assert!(Test::dynamic_setter::<i8>(".c[4].a", 1).is_none());
+    assert!(Test::dynamic_setter_numeric(".c[4].a", 1.0).is_none());
+    let v = test.get(getter!(Test.c[3].a)).unwrap();
+    assert!(v.parse_value::<i8>(".c[4].a").is_none());
+    assert_eq!(v.parse_value::<i8>(".c[3].a"), Some(0));
+
+    // short buffer is reported at the leaf
+    let buf = [];
+    assert_eq!(<Test as RemoteGet>::hydrate(getter!(Test.c[1].b), &buf).err(), Some(Error::BufferTooShort { depth: 3 }));
+
+    let e = Error::WrongVariant { depth: 1 };
+    assert_eq!(e.depth(), 1);
+    assert_eq!(e.nested().depth(), 2);
+    assert_eq!(format!("{}", e), "wrong enum variant at depth 1");
+}
@@ -0,0 +1,143 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Limits {
+    #[remote(unit = "A")]
+    max_current: f32,
+    max_voltage: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Pid {
+    kp: f32,
+    ki: f32,
+    #[remote(flatten)]
+    limits: Limits,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Motor {
+    enabled: bool,
+    #[remote(flatten)]
+    pid: Pid,
+    #[remote(read_only)]
+    temperature: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Drive {
+    Off,
+    Closed {
+        #[remote(flatten)]
+        pid: Pid,
+        target: f32,
+    },
+}
+
+#[test]
+fn flatten_get() {
+    let m = Motor {
+        enabled: true,
+        pid: Pid { kp: 1.0, ki: 0.5, limits: Limits { max_current: 2.0, max_voltage: 12.0 } },
+        temperature: 40.0,
+    };
+
+    let getter = <Motor as RemoteGet>::GetterType::parse_getter(".ki").unwrap();
+    assert!(getter == getter!(Motor.pid.ki));
+    assert_eq!(format!("{}", getter), ".ki");
+    assert_eq!(m.get(getter).unwrap().pid().ki(), 0.5);
+
+    let getter = <Motor as RemoteGet>::GetterType::parse_getter(".max_voltage").unwrap();
+    assert!(getter == getter!(Motor.pid.limits.max_voltage));
+    assert_eq!(format!("{}", getter), ".max_voltage");
+    let value = m.get(getter).unwrap();
+    assert_eq!(value.parse_value::<f32>(".max_voltage"), Some(12.0));
+
+    assert!(<Motor as RemoteGet>::GetterType::parse_getter(".enabled").is_some());
+    assert!(<Motor as RemoteGet>::GetterType::parse_getter(".pid.kp").is_none());
+    assert!(<Motor as RemoteGet>::GetterType::parse_getter(".limits.max_voltage").is_none());
+}
+
+#[test]
+fn flatten_fields() {
+    assert_eq!(
+        Some(FieldsType::Fields(&[".enabled", ".kp", ".ki", ".max_current", ".max_voltage", ".temperature"])),
+        <Motor as RemoteGet>::GetterType::get_fields("")
+    );
+    assert_eq!(Some(FieldsType::Terminal), <Motor as RemoteGet>::GetterType::get_fields(".max_current"));
+    assert_eq!(
+        Some(FieldsType::Fields(&[".kp", ".ki", ".max_current", ".max_voltage", ".target"])),
+        <Drive as RemoteGet>::GetterType::get_fields("::Closed")
+    );
+    assert_eq!(<Motor as RemoteSet>::SetterType::FIELDS, [".enabled", ".kp", ".ki", ".max_current", ".max_voltage"]);
+
+    let m = Motor {
+        enabled: true,
+        pid: Pid { kp: 1.0, ki: 0.5, limits: Limits { max_current: 2.0, max_voltage: 12.0 } },
+        temperature: 40.0,
+    };
+    assert_eq!(Some(FieldsType::Terminal), m.dynamic_fields(".kp", &mut |_| {}));
+    assert_eq!(Some("A"), Motor::get_metadata(".max_current").unwrap().unit);
+}
+
+#[test]
+fn flatten_set() {
+    let mut m = Motor {
+        enabled: true,
+        pid: Pid { kp: 1.0, ki: 0.5, limits: Limits { max_current: 2.0, max_voltage: 12.0 } },
+        temperature: 40.0,
+    };
+    m.set(Motor::dynamic_setter(".kp", 3.0f32).unwrap()).unwrap();
+    m.set(Motor::dynamic_setter_numeric(".max_current", 4.0).unwrap()).unwrap();
+    m.set(setter!(Motor.pid.limits.max_voltage = 24.0)).unwrap();
+    assert_eq!(m.pid.kp, 3.0);
+    assert_eq!(m.pid.limits.max_current, 4.0);
+    assert_eq!(m.pid.limits.max_voltage, 24.0);
+    assert_eq!(format!("{}", setter!(Motor.pid.limits.max_voltage = 24.0)), ".max_voltage24");
+    assert!(Motor::dynamic_setter(".pid.kp", 3.0f32).is_none());
+
+    let mut d = Drive::Closed { pid: Pid { kp: 1.0, ki: 0.0, limits: Limits { max_current: 1.0, max_voltage: 1.0 } }, target: 0.0 };
+    d.set(Drive::dynamic_setter("::Closed.ki", 2.0f32).unwrap()).unwrap();
+    d.set(setter!(Drive::Closed.target = 5.0)).unwrap();
+    assert!(matches!(d, Drive::Closed { pid: Pid { ki, .. }, target } if ki == 2.0 && target == 5.0));
+}
+
+#[test]
+fn flatten_reset() {
+    let mut m = Motor {
+        enabled: true,
+        pid: Pid { kp: 1.0, ki: 0.5, limits: Limits { max_current: 2.0, max_voltage: 12.0 } },
+        temperature: 40.0,
+    };
+    m.set(Motor::dynamic_setter(".ki reset", ()).unwrap()).unwrap();
+    assert_eq!(m.pid.ki, 0.0);
+    m.reset(getter!(Motor.pid.limits.max_current)).unwrap();
+    assert_eq!(m.pid.limits.max_current, 0.0);
+
+    let mut paths = Vec::new();
+    m.diff_default(&FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, [".enabled", ".kp", ".max_voltage"]);
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Supply {
+    max: f32,
+    #[remote(flatten)]
+    limits: Limits,
+    max_current_peak: f32,
+}
+
+#[test]
+fn flatten_prefix() {
+    let mut s = Supply { max: 1.0, limits: Limits { max_current: 2.0, max_voltage: 3.0 }, max_current_peak: 4.0 };
+    s.set(Supply::dynamic_setter(".max_current", 5.0f32).unwrap()).unwrap();
+    s.set(Supply::dynamic_setter(".max_current_peak", 6.0f32).unwrap()).unwrap();
+    s.set(Supply::dynamic_setter(".max", 7.0f32).unwrap()).unwrap();
+    assert_eq!((s.max, s.limits.max_current, s.max_current_peak), (7.0, 5.0, 6.0));
+
+    let getter = <Supply as RemoteGet>::GetterType::parse_getter(".max_current_peak").unwrap();
+    assert!(getter == getter!(Supply.max_current_peak));
+    let getter = <Supply as RemoteGet>::GetterType::parse_getter(".max_current").unwrap();
+    assert!(getter == getter!(Supply.limits.max_current));
+    assert_eq!(Some(FieldsType::Terminal), <Supply as RemoteGet>::GetterType::get_fields(".max_current_peak"));
+}
@@ -0,0 +1,127 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter, Default)]
+pub struct Calibration {
+    offset: f32,
+    scale: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Channel<T: RemoteGet + RemoteSet> {
+    gain: T,
+    enabled: bool,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Bank<const N: usize> {
+    slots: [u8; N],
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Source<T> {
+    Off,
+    Fixed(T),
+    Ramp { from: T, to: T, steps: u16 },
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Mixer<T: RemoteGet + RemoteSet, const N: usize> {
+    channels: [Channel<T>; N],
+    source: Source<T>,
+    #[remote(skip)]
+    label: &'static str,
+}
+
+#[test]
+fn test_generic_struct() {
+    let mut channel = Channel { gain: 1.0f32, enabled: false };
+    channel.set(setter!(Channel::<f32>.gain = 2.0)).unwrap();
+    channel.set(setter!(Channel::<f32>.enabled = true)).unwrap();
+    let v = channel.get(getter!(Channel::<f32>.gain)).unwrap();
+    assert_eq!(v.gain(), 2.0);
+    assert!(channel.enabled);
+
+    // the type parameter can be left to inference
+    channel.set(setter!(Channel.gain = 3.0)).unwrap();
+    assert_eq!(channel.gain, 3.0);
+
+    let mut channel = Channel { gain: Calibration::default(), enabled: true };
+    channel.set(setter!(Channel::<Calibration>.gain.scale = 0.5)).unwrap();
+    let v = channel.get(getter!(Channel::<Calibration>.gain.scale)).unwrap();
+    assert_eq!(v.gain().scale(), 0.5);
+
+    let path = ".gain.offset";
+    channel.set(Channel::<Calibration>::dynamic_setter_numeric(path, 4.0).unwrap()).unwrap();
+    let getter = Channel::<Calibration>::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = channel.get(getter).unwrap();
+    assert_eq!(value.parse_value::<f32>(path), Some(4.0));
+
+    let mut buf = [0; 4];
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Channel<Calibration> as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+}
+
+#[test]
+fn test_const_generic() {
+    let mut bank = Bank { slots: [0; 4] };
+    bank.set(setter!(Bank::<4>.slots[2] = 7)).unwrap();
+    let v = bank.get(getter!(Bank::<4>.slots[2])).unwrap();
+    assert_eq!(v.slots()[2], 7);
+    assert_eq!(bank.slots, [0, 0, 7, 0]);
+
+    assert_eq!(bank.set(setter!(Bank::<4>.slots[4] = 1)), Err(Error::IndexOutOfBounds { depth: 1 }));
+    assert_eq!(Some(FieldsType::Arr(4)), <Bank<4> as RemoteGet>::GetterType::get_fields(".slots"));
+}
+
+#[test]
+fn test_generic_enum() {
+    let mut mixer = Mixer {
+        channels: [Channel { gain: 0u16, enabled: false }, Channel { gain: 0, enabled: false }],
+        source: Source::Off,
+        label: "main",
+    };
+
+    mixer.set(setter!(Mixer::<u16, 2>.channels[1].gain = 5)).unwrap();
+    assert_eq!(mixer.channels[1].gain, 5);
+
+    assert_eq!(mixer.set(setter!(Mixer::<u16, 2>.source::Fixed = 1)), Err(Error::WrongVariant { depth: 1 }));
+    mixer.source = Source::Ramp { from: 0, to: 10, steps: 4 };
+    mixer.set(setter!(Mixer::<u16, 2>.source::Ramp.to = 20)).unwrap();
+    let v = mixer.get(getter!(Mixer::<u16, 2>.source::Ramp.to)).unwrap();
+    assert_eq!(v.source().Ramp().to(), 20);
+
+    let v = mixer.get(getter!(Mixer::<u16, 2>.source.var)).unwrap();
+    assert!(v.source() == <Source<u16> as RemoteGet>::ValueType::Ramp);
+
+    mixer.set(setter!(Mixer::<u16, 2>.source::Off)).unwrap();
+    assert!(matches!(mixer.source, Source::Off));
+    assert_eq!(mixer.label, "main");
+
+    assert_eq!(Some(FieldsType::Fields(&[".from", ".to", ".steps"])), <Mixer<u16, 2> as RemoteGet>::GetterType::get_fields(".source::Ramp"));
+}
+
+#[derive(RemoteSetter, RemoteGetter, Clone, Copy, PartialEq, Debug)]
+#[remote(replace)]
+pub enum Level<T> {
+    Off,
+    On(T),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Output<T: Copy + RemoteGet + RemoteSet + 'static> {
+    level: Level<T>,
+}
+
+#[test]
+fn test_generic_replace() {
+    let mut output = Output { level: Level::Off };
+    output.set(setter!(Output::<u8>.level = Level::On(3))).unwrap();
+    assert_eq!(output.level, Level::On(3));
+    output.set(setter!(Output::<u8>.level::On = 4)).unwrap();
+    assert_eq!(output.level, Level::On(4));
+    output.set(Output::<u8>::dynamic_setter(".level", Level::<u8>::Off).unwrap()).unwrap();
+    assert_eq!(output.level, Level::Off);
+}
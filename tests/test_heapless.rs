@@ -0,0 +1,160 @@
+#![cfg(feature = "heapless")]
+
+use remote_obj::prelude::*;
+use remote_obj::StringBuf;
+use heapless::{String, Vec};
+
+#[derive(RemoteSetter, RemoteGetter, Default, PartialEq, Debug)]
+pub struct Point {
+    x: i16,
+    y: i16,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Log {
+    samples: Vec<u16, 4>,
+    points: Vec<Point, 2>,
+    name: String<8>,
+}
+
+#[test]
+fn test_vec() {
+    let mut log = Log {
+        samples: Vec::from_slice(&[1, 2]).unwrap(),
+        points: Vec::new(),
+        name: String::try_from("log").unwrap(),
+    };
+
+    log.set(setter!(Log.samples[1] = 5)).unwrap();
+    let v = log.get(getter!(Log.samples[1])).unwrap();
+    assert_eq!(v.samples()[1], 5);
+
+    // elements past the current length can't be accessed
+    assert_eq!(log.set(setter!(Log.samples[2] = 5)), Err(Error::IndexOutOfBounds { depth: 1 }));
+    assert_eq!(log.get(getter!(Log.samples[2])).err(), Some(Error::IndexOutOfBounds { depth: 1 }));
+
+    log.set(setter!(Log.samples.push = 3)).unwrap();
+    log.set(setter!(Log.samples.push = 4)).unwrap();
+    assert_eq!(log.samples, [1, 5, 3, 4]);
+    assert_eq!(log.set(setter!(Log.samples.push = 6)), Err(Error::IndexOutOfBounds { depth: 1 }));
+    let v = log.get(getter!(Log.samples.len)).unwrap();
+    assert_eq!(v.samples().len(), 4);
+
+    log.set(setter!(Log.samples.pop)).unwrap();
+    assert_eq!(log.samples, [1, 5, 3]);
+    log.set(setter!(Log.samples.truncate = 1)).unwrap();
+    assert_eq!(log.samples, [1]);
+    log.set(setter!(Log.samples.pop)).unwrap();
+    assert_eq!(log.set(setter!(Log.samples.pop)), Err(Error::IndexOutOfBounds { depth: 1 }));
+
+    log.set(setter!(Log.points.push.y = 2)).unwrap();
+    log.set(setter!(Log.points[0].x = 1)).unwrap();
+    assert_eq!(log.points, [Point { x: 1, y: 2 }]);
+    let v = log.get(getter!(Log.points[0].y)).unwrap();
+    assert_eq!(v.points()[0].y(), 2);
+}
+
+#[test]
+fn test_vec_dynamic() {
+    let mut log = Log {
+        samples: Vec::from_slice(&[1, 2]).unwrap(),
+        points: Vec::new(),
+        name: String::try_from("log").unwrap(),
+    };
+
+    log.set(Log::dynamic_setter::<u16>(".samples.push", 7).unwrap()).unwrap();
+    log.set(Log::dynamic_setter_numeric(".samples[0]", 8.0).unwrap()).unwrap();
+    log.set(Log::dynamic_setter_numeric(".points.push.x", 3.0).unwrap()).unwrap();
+    assert_eq!(log.samples, [8, 2, 7]);
+    assert_eq!(log.points, [Point { x: 3, y: 0 }]);
+    log.set(Log::dynamic_setter(".samples.pop", ()).unwrap()).unwrap();
+    log.set(Log::dynamic_setter(".samples.truncate", 1usize).unwrap()).unwrap();
+    assert_eq!(log.samples, [8]);
+
+    // displayed actions can be parsed back
+    let truncate = setter!(Log.samples.truncate = 1);
+    let (path, len) = format!("{}", truncate).split_once(" = ").map(|(p, v)| (p.to_string(), v.to_string())).unwrap();
+    assert_eq!(path, ".samples.truncate");
+    let parsed = Log::dynamic_setter(&path, len.parse::<usize>().unwrap()).unwrap();
+    assert_eq!(format!("{}", parsed), format!("{}", truncate));
+    let parsed = Log::dynamic_setter_numeric(&path, len.parse().unwrap()).unwrap();
+    assert_eq!(format!("{}", parsed), format!("{}", truncate));
+    // lengths given as numbers have to be non-negative integers
+    for len in [-1.0, 0.5, f64::NAN] {
+        let truncate = Log::dynamic_setter_numeric(".samples.truncate", len).unwrap();
+        assert_eq!(log.set(truncate), Err(Error::OutOfRange { depth: 1 }));
+    }
+    assert_eq!(log.samples, [8]);
+    let pop = format!("{}", setter!(Log.samples.pop));
+    assert_eq!(format!("{}", Log::dynamic_setter(&pop, ()).unwrap()), pop);
+
+    assert!(Log::dynamic_setter(".samples[4]", 1u16).is_none());
+    assert!(Log::dynamic_setter(".samples.pop", 1u16).is_none());
+
+    let path = ".samples.len";
+    let getter = Log::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = log.get(getter).unwrap();
+    assert_eq!(value.parse_value::<usize>(path), Some(1));
+    assert_eq!(value.as_float(), Some(1.0));
+    let mut buf = [0; 8];
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Log as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+
+    let path = ".points[0].x";
+    let getter = Log::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = log.get(getter).unwrap();
+    assert_eq!(value.parse_value::<i16>(path), Some(3));
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Log as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+
+    assert_eq!(Some(FieldsType::Arr(4)), <Log as RemoteGet>::GetterType::get_fields(".samples"));
+    assert_eq!(Some(FieldsType::Terminal), <Log as RemoteGet>::GetterType::get_fields(".samples.len"));
+    assert_eq!(Some(FieldsType::Fields(&[".x", ".y"])), <Log as RemoteGet>::GetterType::get_fields(".points[1]"));
+}
+
+#[test]
+fn test_string() {
+    let mut log = Log {
+        samples: Vec::from_slice(&[1, 2]).unwrap(),
+        points: Vec::new(),
+        name: String::try_from("log").unwrap(),
+    };
+
+    log.set(setter!(Log.name = String::try_from("motor").unwrap())).unwrap();
+    assert_eq!(log.name, "motor");
+    log.set(Log::dynamic_setter(".name", "pump").unwrap()).unwrap();
+    assert_eq!(log.name, "pump");
+    // too long for the capacity
+    assert!(Log::dynamic_setter(".name", "compressor").is_none());
+
+    let getter = getter!(Log.name);
+    let value = log.get(getter).unwrap();
+    assert_eq!(value.name().as_str(), "pump");
+    assert_eq!(value.parse_value::<String<8>>(".name").unwrap(), "pump");
+
+    // length prefixed on the wire
+    let mut buf = [0; 10];
+    assert_eq!(value.dehydrated_len(), 6);
+    assert_eq!(value.dehydrate(&mut buf), Some(6));
+    assert_eq!(&buf[..6], b"\x04\x00pump");
+    assert_eq!(value.dehydrate(&mut buf[..5]), None);
+    let (rehydrated_v, length) = <Log as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(length, 6);
+    assert!(rehydrated_v == value);
+    assert_eq!(<Log as RemoteGet>::hydrate(getter, &buf[..5]).err(), Some(Error::BufferTooShort { depth: 1 }));
+    assert_eq!(<Log as RemoteGet>::hydrate(getter, b"\x09\x00").err(), Some(Error::InvalidEncoding { depth: 1 }));
+    assert_eq!(<Log as RemoteGet>::hydrate(getter, b"\x01\x00\xff").err(), Some(Error::InvalidEncoding { depth: 1 }));
+
+    let config = bincode::config::standard();
+    let mut buf = [0; 16];
+    let setter = StringBuf::<8>::new("fan").unwrap();
+    let len = bincode::encode_into_slice(setter, &mut buf, config).unwrap();
+    let (decoded, _): (StringBuf<8>, usize) = bincode::decode_from_slice(&buf[..len], config).unwrap();
+    assert_eq!(decoded, setter);
+}
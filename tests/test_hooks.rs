@@ -0,0 +1,153 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(validate = "Filter::check", on_set = "Filter::changed")]
+pub struct Filter {
+    #[remote(on_set = "Filter::recompute")]
+    cutoff: f32,
+    #[remote(validate = "Filter::check_order", range(min = 1, max = 8), default = 2)]
+    order: u8,
+    locked: bool,
+    #[remote(skip)]
+    coefficient: f32,
+    #[remote(skip)]
+    writes: u32,
+}
+
+impl Filter {
+    fn check(&self, x: &FilterSetter) -> Result<(), Error> {
+        match x {
+            FilterSetter::locked(_) => Ok(()),
+            _ if self.locked => Err(Error::WrongVariant { depth: 0 }),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_order(&self, x: &u8) -> Result<(), Error> {
+        if x & 1 == 0 {
+            Ok(())
+        } else {
+            Err(Error::OutOfRange { depth: 0 })
+        }
+    }
+
+    fn recompute(&mut self) {
+        self.coefficient = self.cutoff / 2.0;
+    }
+
+    fn changed(&mut self) {
+        self.writes += 1;
+    }
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Stage {
+    Off,
+    Gain {
+        #[remote(validate = "Stage::check_gain", on_set = "Stage::count")]
+        gain: f32,
+        #[remote(skip)]
+        writes: u32,
+    },
+    Trim(#[remote(validate = "Stage::check_gain", on_set = "Stage::round_trim")] f32),
+}
+
+impl Stage {
+    fn check_gain(&self, x: &f32) -> Result<(), Error> {
+        if x.is_finite() {
+            Ok(())
+        } else {
+            Err(Error::OutOfRange { depth: 0 })
+        }
+    }
+
+    fn round_trim(&mut self) {
+        if let Stage::Trim(trim) = self {
+            *trim = trim.round();
+        }
+    }
+
+    fn count(&mut self) {
+        if let Stage::Gain { writes, .. } = self {
+            *writes += 1;
+        }
+    }
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Chain {
+    filter: Filter,
+    stage: Stage,
+}
+
+#[test]
+fn test_hooks() {
+    let mut chain = Chain {
+        filter: Filter { cutoff: 0.0, order: 2, locked: false, coefficient: 0.0, writes: 0 },
+        stage: Stage::Gain { gain: 1.0, writes: 0 },
+    };
+
+    chain.set(setter!(Chain.filter.cutoff = 100.0)).unwrap();
+    assert_eq!(chain.filter.coefficient, 50.0);
+    assert_eq!(chain.filter.writes, 1);
+
+    // vetoed by the field validator, the container hook is not called
+    assert_eq!(chain.set(setter!(Chain.filter.order = 3)), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(chain.filter.order, 2);
+    assert_eq!(chain.filter.writes, 1);
+    chain.set(setter!(Chain.filter.order = 4)).unwrap();
+    assert_eq!(chain.filter.writes, 2);
+    // rejected by the range check after validation
+    assert_eq!(chain.set(setter!(Chain.filter.order = 10)), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(chain.filter.writes, 2);
+
+    // cross-field check in the container validator
+    chain.set(setter!(Chain.filter.locked = true)).unwrap();
+    assert_eq!(chain.set(setter!(Chain.filter.cutoff = 10.0)), Err(Error::WrongVariant { depth: 1 }));
+    assert_eq!(chain.filter.cutoff, 100.0);
+    chain.set(Chain::dynamic_setter(".filter.locked", false).unwrap()).unwrap();
+    chain.set(Chain::dynamic_setter_numeric(".filter.cutoff", 10.0).unwrap()).unwrap();
+    assert_eq!(chain.filter.coefficient, 5.0);
+    assert_eq!(chain.filter.writes, 5);
+
+    chain.set(setter!(Chain.stage::Gain.gain = 2.0)).unwrap();
+    assert_eq!(chain.set(setter!(Chain.stage::Gain.gain = f32::INFINITY)), Err(Error::OutOfRange { depth: 2 }));
+    assert!(matches!(chain.stage, Stage::Gain { gain, writes: 1 } if gain == 2.0));
+    chain.set(setter!(Chain.stage::Off)).unwrap();
+    assert_eq!(chain.set(setter!(Chain.stage::Gain.gain = 1.0)), Err(Error::WrongVariant { depth: 1 }));
+
+    // hooks of the field of a newtype variant
+    chain.stage = Stage::Trim(0.0);
+    chain.set(setter!(Chain.stage::Trim = 2.6)).unwrap();
+    assert!(matches!(chain.stage, Stage::Trim(trim) if trim == 3.0));
+    assert_eq!(chain.set(setter!(Chain.stage::Trim = f32::NAN)), Err(Error::OutOfRange { depth: 1 }));
+    assert!(matches!(chain.stage, Stage::Trim(trim) if trim == 3.0));
+}
+
+#[test]
+fn test_hooks_reset() {
+    let mut chain = Chain {
+        filter: Filter { cutoff: 8.0, order: 4, locked: false, coefficient: 0.0, writes: 0 },
+        stage: Stage::Gain { gain: 1.0, writes: 0 },
+    };
+
+    // resets call `on_set` of the fields they write, but no validators
+    chain.set(setter!(Chain.filter.cutoff reset)).unwrap();
+    assert_eq!(chain.filter.coefficient, 0.0);
+    assert_eq!(chain.filter.writes, 1);
+    chain.filter.cutoff = 8.0;
+    chain.set(setter!(Chain.filter.order reset)).unwrap();
+    assert_eq!(chain.filter.coefficient, 0.0);
+    assert_eq!(chain.filter.writes, 2);
+    chain.set(setter!(Chain.filter reset)).unwrap();
+    assert_eq!((chain.filter.cutoff, chain.filter.order, chain.filter.coefficient), (0.0, 2, 0.0));
+    assert_eq!(chain.filter.writes, 3);
+
+    chain.set(setter!(Chain.stage::Gain.gain reset)).unwrap();
+    chain.set(setter!(Chain.stage::Gain reset)).unwrap();
+    chain.set(setter!(Chain.stage reset)).unwrap();
+    assert!(matches!(chain.stage, Stage::Gain { gain, writes: 3 } if gain == 0.0));
+    chain.set(setter!(Chain.stage::Off)).unwrap();
+    chain.set(setter!(Chain.stage reset)).unwrap();
+    assert!(matches!(chain.stage, Stage::Off));
+}
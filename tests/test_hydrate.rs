@@ -12,6 +12,20 @@ pub struct NestedTest {
     b: Test,
 }
 
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum TestEnum {
+    A,
+    B(Test),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Wide {
+    a: u32,
+    b: f64,
+    c: TestEnum,
+    d: [u16; 4],
+}
+
 #[test]
 fn test_hydrate() {
     let mut nested = NestedTest {
@@ -39,4 +53,38 @@ fn test_hydrate() {
     let (rehydrated_v, length) = <NestedTest as RemoteGet>::hydrate(g, &buf).unwrap();
     assert_eq!(length, 1);
     assert_eq!(rehydrated_v.a().a(), 1);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_dehydrated_len() {
+    let wide = Wide {
+        a: 1,
+        b: 2.0,
+        c: TestEnum::B(Test {
+            a: 3,
+            b: 4
+        }),
+        d: [5; 4]
+    };
+
+    let cases = [
+        (getter!(Wide.a), 4),
+        (getter!(Wide.b), 8),
+        (getter!(Wide.c.var), 1),
+        (getter!(Wide.c::B.a), 1),
+        (getter!(Wide.d[2]), 2),
+    ];
+
+    for (g, len) in cases {
+        let v = wide.get(g).unwrap();
+        assert_eq!(v.dehydrated_len(), len);
+
+        let mut buf = [0; 8];
+        assert_eq!(v.dehydrate(&mut buf[..len - 1]), None);
+        assert_eq!(v.dehydrate(&mut buf[..len]), Some(len));
+
+        let (rehydrated_v, length) = <Wide as RemoteGet>::hydrate(g, &buf).unwrap();
+        assert_eq!(length, len);
+        assert!(rehydrated_v == v);
+    }
+}
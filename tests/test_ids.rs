@@ -0,0 +1,109 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter, Default)]
+pub struct Gains {
+    #[remote(id = 1)]
+    kp: f32,
+    #[remote(id = 2)]
+    ki: f32,
+    unnumbered: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Mode {
+    #[remote(id = 0)]
+    Off,
+    #[remote(id = 1)]
+    Pid(Gains),
+    #[remote(id = 2)]
+    Ramp {
+        #[remote(id = 5)]
+        rate: f32,
+    },
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Drive {
+    // declared out of order, ids don't depend on the position of fields
+    #[remote(id = 0x20)]
+    channels: [Gains; 2],
+    #[remote(id = 0x10)]
+    gains: Gains,
+    #[remote(id = 0x30)]
+    mode: Mode,
+    #[remote(id = 0x40)]
+    timeout: Option<u16>,
+}
+
+fn ids<G: Getter>(getter: G) -> Vec<u32> {
+    let mut buf = [0; 8];
+    let len = getter.to_ids(&mut buf).unwrap();
+    buf[..len].to_vec()
+}
+
+#[test]
+fn test_getter_ids() {
+    assert_eq!(ids(getter!(Drive.gains.ki)), [0x10, 2]);
+    assert_eq!(ids(getter!(Drive.channels[1].kp)), [0x20, 1, 1]);
+    assert_eq!(ids(getter!(Drive.mode.var)), [0x30]);
+    assert_eq!(ids(getter!(Drive.mode::Pid.kp)), [0x30, 1, 1]);
+    assert_eq!(ids(getter!(Drive.mode::Ramp.rate)), [0x30, 2, 5]);
+    assert_eq!(ids(getter!(Drive.timeout.var)), [0x40]);
+    assert_eq!(ids(getter!(Drive.timeout::Some)), [0x40, 1]);
+
+    for path in [".gains.ki", ".channels[1].kp", ".mode::Ramp.rate", ".timeout::Some"] {
+        let getter = Drive::dynamic_getter(path).unwrap();
+        assert!(Drive::id_getter(&ids(getter)) == Some(getter));
+    }
+    assert!(Drive::id_getter(&[0x30]) == Some(getter!(Drive.mode.var)));
+
+    // fields without an id have no numeric path
+    let mut buf = [0; 8];
+    assert_eq!(getter!(Drive.gains.unnumbered).to_ids(&mut buf), None);
+    // buffer too short
+    assert_eq!(getter!(Drive.mode::Pid.kp).to_ids(&mut buf[..2]), None);
+
+    assert!(Drive::id_getter(&[0x10, 3]).is_none());
+    assert!(Drive::id_getter(&[0x20, 2, 1]).is_none());
+    assert!(Drive::id_getter(&[0x10]).is_none());
+    assert!(Drive::id_getter(&[0x10, 1, 0]).is_none());
+}
+
+#[test]
+fn test_setter_ids() {
+    let mut drive = Drive {
+        channels: Default::default(),
+        gains: Gains::default(),
+        mode: Mode::Pid(Gains::default()),
+        timeout: None,
+    };
+
+    drive.set(Drive::id_setter(&[0x10, 1], 2.0f32).unwrap()).unwrap();
+    assert_eq!(drive.gains.kp, 2.0);
+    drive.set(Drive::id_setter_numeric(&[0x20, 1, 2], 0.5).unwrap()).unwrap();
+    assert_eq!(drive.channels[1].ki, 0.5);
+    drive.set(Drive::id_setter_numeric(&[0x30, 1, 1], 3.0).unwrap()).unwrap();
+    assert!(matches!(drive.mode, Mode::Pid(Gains { kp, .. }) if kp == 3.0));
+
+    // unit variants and `::None` take `()`
+    assert!(Drive::id_setter(&[0x30, 0], 1.0f32).is_none());
+    drive.set(Drive::id_setter(&[0x30, 0], ()).unwrap()).unwrap();
+    assert!(matches!(drive.mode, Mode::Off));
+    drive.set(Drive::id_setter(&[0x40], Some(5u16)).unwrap()).unwrap();
+    assert_eq!(drive.timeout, Some(5));
+    drive.set(Drive::id_setter_numeric(&[0x40, 1], 6.0).unwrap()).unwrap();
+    assert_eq!(drive.timeout, Some(6));
+    drive.set(Drive::id_setter(&[0x40, 0], ()).unwrap()).unwrap();
+    assert_eq!(drive.timeout, None);
+
+    let mut buf = [0; 8];
+    let setter = setter!(Drive.mode::Ramp.rate = 1.0);
+    let len = setter.to_ids(&mut buf).unwrap();
+    assert_eq!(buf[..len], [0x30, 2, 5]);
+    let len = setter!(Drive.mode::Off).to_ids(&mut buf).unwrap();
+    assert_eq!(buf[..len], [0x30, 0]);
+    assert_eq!(setter!(Drive.gains.unnumbered = 1.0).to_ids(&mut buf), None);
+
+    assert!(Drive::id_setter(&[0x10, 1], 2.0f64).is_none());
+    assert!(Drive::id_setter_numeric(&[0x10, 7], 2.0).is_none());
+}
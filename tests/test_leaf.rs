@@ -0,0 +1,118 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Test {
+    a: bool,
+    b: char,
+    c: [bool; 4],
+}
+
+#[test]
+fn test_bool_char() {
+    let mut test = Test {
+        a: false,
+        b: 'a',
+        c: [false; 4],
+    };
+
+    test.set(setter!(Test.a = true)).unwrap();
+    assert!(test.get(getter!(Test.a)).unwrap().a());
+    test.set(setter!(Test.b = 'é')).unwrap();
+    assert_eq!(test.get(getter!(Test.b)).unwrap().b(), 'é');
+
+    test.set(Test::dynamic_setter_numeric(".a", 0.0).unwrap()).unwrap();
+    assert!(!test.a);
+    test.set(Test::dynamic_setter_numeric(".c[2]", 2.0).unwrap()).unwrap();
+    assert!(test.c[2]);
+    test.set(Test::dynamic_setter_numeric(".b", 0x42 as f64).unwrap()).unwrap();
+    assert_eq!(test.b, 'B');
+    assert!(Test::dynamic_setter_numeric(".b", 0xd800 as f64).is_none());
+    assert!(Test::dynamic_setter_numeric(".b", -1.0).is_none());
+    test.set(Test::dynamic_setter::<bool>(".a", true).unwrap()).unwrap();
+    assert!(test.a);
+
+    let v = test.get(getter!(Test.a)).unwrap();
+    assert_eq!(v.as_float(), Some(1.0));
+    assert_eq!(v.parse_value::<bool>(".a"), Some(true));
+    let v = test.get(getter!(Test.b)).unwrap();
+    assert_eq!(v.as_float(), Some(66.0));
+}
+
+#[test]
+fn test_bool_char_hydrate() {
+    let mut test = Test {
+        a: true,
+        b: '€',
+        c: [false; 4],
+    };
+
+    let mut buf = [0; 4];
+    let v = test.get(getter!(Test.a)).unwrap();
+    assert_eq!(v.dehydrated_len(), 1);
+    assert_eq!(v.dehydrate(&mut buf), Some(1));
+    assert_eq!(buf[0], 1);
+    let (rehydrated_v, length) = <Test as RemoteGet>::hydrate(getter!(Test.a), &buf).unwrap();
+    assert_eq!(length, 1);
+    assert!(rehydrated_v == v);
+    assert_eq!(<Test as RemoteGet>::hydrate(getter!(Test.a), &[2]).err(), Some(Error::InvalidEncoding { depth: 1 }));
+
+    for c in ['a', 'é', '€', '🦀'] {
+        test.b = c;
+        let v = test.get(getter!(Test.b)).unwrap();
+        let len = c.len_utf8();
+        assert_eq!(v.dehydrated_len(), len);
+        assert_eq!(v.dehydrate(&mut buf[..len - 1]), None);
+        assert_eq!(v.dehydrate(&mut buf), Some(len));
+        let (rehydrated_v, length) = <Test as RemoteGet>::hydrate(getter!(Test.b), &buf).unwrap();
+        assert_eq!(length, len);
+        assert!(rehydrated_v == v);
+        assert_eq!(<Test as RemoteGet>::hydrate(getter!(Test.b), &buf[..len - 1]).err(), Some(Error::BufferTooShort { depth: 1 }));
+    }
+
+    assert_eq!(<Test as RemoteGet>::hydrate(getter!(Test.b), &[0x80]).err(), Some(Error::InvalidEncoding { depth: 1 }));
+    assert_eq!(<Test as RemoteGet>::hydrate(getter!(Test.b), &[0xed, 0xa0, 0x80]).err(), Some(Error::InvalidEncoding { depth: 1 }));
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Wide {
+    a: u128,
+    b: i128,
+    c: usize,
+    d: isize,
+}
+
+#[test]
+fn test_wide_int() {
+    let mut wide = Wide {
+        a: 0,
+        b: 0,
+        c: 0,
+        d: 0,
+    };
+
+    wide.set(setter!(Wide.a = u128::MAX)).unwrap();
+    wide.set(setter!(Wide.b = i128::MIN)).unwrap();
+    wide.set(Wide::dynamic_setter::<usize>(".c", 12345).unwrap()).unwrap();
+    wide.set(Wide::dynamic_setter_numeric(".d", -5.0).unwrap()).unwrap();
+    assert_eq!(wide.get(getter!(Wide.a)).unwrap().a(), u128::MAX);
+    assert_eq!(wide.get(getter!(Wide.b)).unwrap().b(), i128::MIN);
+    assert_eq!(wide.get(getter!(Wide.d)).unwrap().parse_value::<isize>(".d"), Some(-5));
+
+    let mut buf = [0; 16];
+    for (g, len) in [(getter!(Wide.a), 16), (getter!(Wide.b), 16), (getter!(Wide.c), 8), (getter!(Wide.d), 8)] {
+        let v = wide.get(g).unwrap();
+        assert_eq!(v.dehydrated_len(), len);
+        assert_eq!(v.dehydrate(&mut buf), Some(len));
+        let (rehydrated_v, length) = <Wide as RemoteGet>::hydrate(g, &buf).unwrap();
+        assert_eq!(length, len);
+        assert!(rehydrated_v == v);
+    }
+
+    // pointer sized integers are always 8 bytes on the wire
+    let v = wide.get(getter!(Wide.c)).unwrap();
+    v.dehydrate(&mut buf).unwrap();
+    assert_eq!(buf[..8], 12345u64.to_le_bytes());
+    let v = wide.get(getter!(Wide.d)).unwrap();
+    v.dehydrate(&mut buf).unwrap();
+    assert_eq!(buf[..8], (-5i64).to_le_bytes());
+}
@@ -0,0 +1,76 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Channel {
+    /// Output voltage.
+    ///
+    /// Updated at 1 kHz.
+    #[remote(unit = "V", range(min = 0.0, max = 30.0))]
+    voltage: f32,
+    #[remote(description = "raw ADC samples")]
+    samples: [u16; 4],
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Source {
+    /// Disabled output
+    Off,
+    #[remote(description = "constant current")]
+    Current(#[remote(unit = "A")] f32),
+    Channel {
+        /// Nested channel
+        channel: Channel,
+    },
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(computed(name = "power", ty = "f32", with = "Supply::power", unit = "W"))]
+pub struct Supply {
+    main: Channel,
+    source: Source,
+    enabled: Option<bool>,
+}
+
+impl Supply {
+    fn power(&self) -> f32 {
+        0.0
+    }
+}
+
+#[test]
+fn test_metadata() {
+    let get_metadata = <Supply as RemoteGet>::get_metadata;
+
+    let voltage = get_metadata(".main.voltage").unwrap();
+    assert_eq!(voltage.type_name, "f32");
+    assert_eq!(voltage.doc, Some("Output voltage.\n\nUpdated at 1 kHz."));
+    assert_eq!(voltage.unit, Some("V"));
+    assert_eq!(voltage.description, None);
+    assert_eq!(voltage.range, Some(ValueRange { min: 0.0, max: 30.0 }));
+
+    let samples = get_metadata(".main.samples").unwrap();
+    assert_eq!(samples.description, Some("raw ADC samples"));
+    assert_eq!(samples.type_name, "[u16; 4]");
+    // attributes of a field only apply to the field itself, not its elements
+    assert_eq!(get_metadata(".main.samples[3]"), Some(FieldMetadata::default()));
+    assert_eq!(get_metadata(".main.samples[4]"), None);
+
+    assert_eq!(get_metadata("").unwrap().type_name, "Supply");
+    assert_eq!(get_metadata(".main").unwrap().type_name, "Channel");
+    assert_eq!(get_metadata(".power").unwrap().unit, Some("W"));
+    assert_eq!(get_metadata(".power").unwrap().type_name, "f32");
+    assert_eq!(get_metadata(".enabled").unwrap().type_name, "Option<bool>");
+    assert_eq!(get_metadata(".enabled::Some"), Some(FieldMetadata::default()));
+
+    let current = get_metadata(".source::Current").unwrap();
+    assert_eq!(current.type_name, "f32");
+    assert_eq!(current.description, Some("constant current"));
+    let channel = get_metadata(".source::Channel").unwrap();
+    assert_eq!(channel.type_name, "Source::Channel");
+    assert_eq!(get_metadata(".source::Channel.channel").unwrap().doc, Some("Nested channel"));
+    assert_eq!(get_metadata(".source::Channel.channel.voltage").unwrap().unit, Some("V"));
+    assert_eq!(current.unit, Some("A"));
+    assert_eq!(get_metadata(".source::Off").unwrap().doc, Some("Disabled output"));
+    assert_eq!(get_metadata(".source::Off").unwrap().type_name, "Source::Off");
+    assert_eq!(get_metadata(".missing"), None);
+}
@@ -0,0 +1,155 @@
+use bincode::{Decode, Encode};
+use remote_obj::prelude::*;
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Default, Debug)]
+pub struct Calibration {
+    offset: i16,
+    gain: f32,
+    valid: bool,
+}
+
+/// no `Default`, so it can be written but not reset
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Debug)]
+pub struct Mac([u8; 6]);
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Sensor {
+    #[remote(opaque, default = "Calibration::default()")]
+    cal: Calibration,
+    #[remote(opaque, read_only)]
+    mac: Mac,
+    #[remote(opaque)]
+    peer: Mac,
+    wrapped: Opaque<Calibration>,
+    rate: u16,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Source {
+    Off,
+    Fixed(#[remote(opaque)] Calibration),
+    Auto {
+        #[remote(opaque)]
+        cal: Calibration,
+        rate: u16,
+    },
+}
+
+const CAL: Calibration = Calibration { offset: -3, gain: 1.5, valid: true };
+
+#[test]
+fn opaque_get() {
+    let s = Sensor {
+        cal: CAL,
+        mac: Mac([1, 2, 3, 4, 5, 6]),
+        peer: Mac([0; 6]),
+        wrapped: Opaque(CAL),
+        rate: 100,
+    };
+    let value = s.get(getter!(Sensor.cal)).unwrap();
+    assert_eq!(value.cal(), Opaque(CAL));
+    assert_eq!(value.parse_value::<Calibration>(".cal"), Some(CAL));
+    assert_eq!(value.parse_value::<Opaque<Calibration>>(".cal"), Some(Opaque(CAL)));
+    assert_eq!(s.get(getter!(Sensor.mac)).unwrap().mac().0, Mac([1, 2, 3, 4, 5, 6]));
+    assert_eq!(s.get(getter!(Sensor.wrapped)).unwrap().wrapped().0, CAL);
+
+    // opaque values are leaves
+    assert_eq!(Some(FieldsType::Terminal), <Sensor as RemoteGet>::GetterType::get_fields(".cal"));
+    assert!(<Sensor as RemoteGet>::GetterType::parse_getter(".cal.gain").is_none());
+    assert_eq!(Sensor::get_metadata(".cal").unwrap().type_name, "Calibration");
+}
+
+#[test]
+fn opaque_hydrate() {
+    let s = Sensor {
+        cal: CAL,
+        mac: Mac([1, 2, 3, 4, 5, 6]),
+        peer: Mac([0; 6]),
+        wrapped: Opaque(CAL),
+        rate: 100,
+    };
+    let mut buf = [0u8; 32];
+    for path in [".cal", ".mac", ".wrapped"] {
+        let getter = <Sensor as RemoteGet>::GetterType::parse_getter(path).unwrap();
+        let value = s.get(getter).unwrap();
+        let len = value.dehydrate(&mut buf).unwrap();
+        assert_eq!(len, value.dehydrated_len());
+        assert!(Sensor::hydrate(getter, &buf[..len]).unwrap() == (value, len));
+        assert!(Sensor::hydrate(getter, &buf[..len - 1]) == Err(Error::BufferTooShort { depth: 1 }));
+    }
+
+    let value = s.get(getter!(Sensor.cal)).unwrap();
+    assert_eq!(value.dehydrate(&mut buf[..2]), None);
+    let len = bincode::encode_into_slice(CAL, &mut buf, bincode::config::standard()).unwrap();
+    assert_eq!(value.dehydrated_len(), len);
+}
+
+#[test]
+fn opaque_set() {
+    let mut s = Sensor {
+        cal: CAL,
+        mac: Mac([1, 2, 3, 4, 5, 6]),
+        peer: Mac([0; 6]),
+        wrapped: Opaque(CAL),
+        rate: 100,
+    };
+    let cal = Calibration { offset: 7, gain: 0.5, valid: false };
+    s.set(setter!(Sensor.cal = cal)).unwrap();
+    assert_eq!(s.cal, cal);
+    s.set(Sensor::dynamic_setter(".wrapped", cal).unwrap()).unwrap();
+    assert_eq!(s.wrapped.0, cal);
+    s.set(Sensor::dynamic_setter(".cal", Opaque(CAL)).unwrap()).unwrap();
+    assert_eq!(s.cal, CAL);
+    assert!(Sensor::dynamic_setter_numeric(".cal", 1.0).is_none());
+    assert!(Sensor::dynamic_setter(".cal", 1.0f32).is_none());
+    s.set(setter!(Sensor.peer = Mac([6; 6]))).unwrap();
+    assert_eq!(s.peer, Mac([6; 6]));
+
+    let setter = setter!(Sensor.cal = Calibration { offset: 1, gain: 0.0, valid: true });
+    assert_eq!(format!("{}", setter), ".cal0x020000000001");
+}
+
+#[test]
+fn opaque_reset() {
+    let mut s = Sensor {
+        cal: CAL,
+        mac: Mac([1, 2, 3, 4, 5, 6]),
+        peer: Mac([0; 6]),
+        wrapped: Opaque(CAL),
+        rate: 100,
+    };
+    let mut paths = Vec::new();
+    s.diff_default(&FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    // values without a `#[remote(default = ..)]` are always reported
+    assert_eq!(paths, [".cal", ".peer", ".wrapped", ".rate"]);
+
+    s.set(setter!(Sensor.cal reset)).unwrap();
+    assert_eq!(s.cal, Calibration::default());
+    assert_eq!(s.set(setter!(Sensor.peer reset)), Err(Error::NotResettable { depth: 1 }));
+
+    // the other fields are still reset
+    s.cal = CAL;
+    assert_eq!(s.set(setter!(Sensor reset)), Err(Error::NotResettable { depth: 1 }));
+    assert_eq!((s.cal, s.rate), (Calibration::default(), 0));
+    assert_eq!(s.wrapped.0, CAL);
+    assert_eq!(s.mac, Mac([1, 2, 3, 4, 5, 6]));
+}
+
+#[test]
+fn opaque_enum() {
+    let mut source = Source::Fixed(CAL);
+    assert_eq!(source.get(getter!(Source::Fixed)).unwrap().parse_value::<Calibration>("::Fixed"), Some(CAL));
+    source.set(setter!(Source::Fixed = Calibration::default())).unwrap();
+    assert!(matches!(source, Source::Fixed(cal) if cal == Calibration::default()));
+
+    let mut source = Source::Auto { cal: CAL, rate: 10 };
+    let value = source.get(getter!(Source::Auto.cal)).unwrap();
+    assert_eq!(value.parse_value::<Calibration>("::Auto.cal"), Some(CAL));
+    source.set(Source::dynamic_setter("::Auto.cal", Calibration::default()).unwrap()).unwrap();
+    assert!(matches!(source, Source::Auto { cal, rate: 10 } if cal == Calibration::default()));
+    assert_eq!(Some(FieldsType::Terminal), source.dynamic_fields("::Auto.cal", &mut |_| {}));
+
+    let mut paths = Vec::new();
+    Source::Fixed(CAL).diff(&Source::Fixed(Calibration::default()), &FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, ["::Fixed"]);
+}
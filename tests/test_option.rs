@@ -0,0 +1,155 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter, Default, Debug, PartialEq)]
+pub struct Calibration {
+    offset: f32,
+    scale: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Settings {
+    timeout: Option<u16>,
+    calibration: Option<Calibration>,
+}
+
+#[test]
+fn test_option_leaf() {
+    let mut settings = Settings {
+        timeout: None,
+        calibration: None,
+    };
+
+    let v = settings.get(getter!(Settings.timeout.var)).unwrap();
+    assert!(!v.timeout().is_some());
+    assert_eq!(settings.get(getter!(Settings.timeout::Some)).err(), Some(Error::WrongVariant { depth: 1 }));
+
+    // writing into `::Some` requires the value to be present
+    assert_eq!(settings.set(setter!(Settings.timeout::Some = 5)), Err(Error::WrongVariant { depth: 1 }));
+    settings.set(setter!(Settings.timeout = Some(5))).unwrap();
+    assert_eq!(settings.timeout, Some(5));
+    settings.set(setter!(Settings.timeout::Some = 6)).unwrap();
+    assert_eq!(settings.timeout, Some(6));
+
+    let v = settings.get(getter!(Settings.timeout.var)).unwrap();
+    assert!(v.timeout().is_some());
+    let v = settings.get(getter!(Settings.timeout::Some)).unwrap();
+    assert_eq!(v.timeout().Some(), 6);
+
+    settings.set(setter!(Settings.timeout::None)).unwrap();
+    assert_eq!(settings.timeout, None);
+}
+
+#[test]
+fn test_option_struct() {
+    let mut settings = Settings {
+        timeout: None,
+        calibration: None,
+    };
+
+    assert!(settings.set(setter!(Settings.calibration::Some.scale = 2.0)).is_err());
+    // a partial setter can't build a value to insert
+    let scale = setter!(Settings.calibration = Some(CalibrationSetter::default().make_scale(|_| 2.0)));
+    assert_eq!(settings.set(scale), Err(Error::WrongVariant { depth: 1 }));
+    assert_eq!(settings.calibration, None);
+
+    settings.calibration = Some(Calibration { offset: 3.0, scale: 1.0 });
+    settings.set(scale).unwrap();
+    assert_eq!(settings.calibration, Some(Calibration { offset: 3.0, scale: 2.0 }));
+    settings.set(setter!(Settings.calibration::Some.offset = 1.0)).unwrap();
+    assert_eq!(settings.calibration, Some(Calibration { offset: 1.0, scale: 2.0 }));
+
+    let v = settings.get(getter!(Settings.calibration::Some.scale)).unwrap();
+    assert_eq!(v.calibration().Some().scale(), 2.0);
+
+    settings.set(setter!(Settings.calibration::None)).unwrap();
+    assert_eq!(settings.calibration, None);
+}
+
+#[test]
+fn test_option_dynamic() {
+    let mut settings = Settings {
+        timeout: Some(1),
+        calibration: Some(Calibration::default()),
+    };
+
+    settings.set(Settings::dynamic_setter::<u16>(".timeout::Some", 2).unwrap()).unwrap();
+    assert_eq!(settings.timeout, Some(2));
+    settings.set(Settings::dynamic_setter(".timeout::None", ()).unwrap()).unwrap();
+    assert_eq!(settings.timeout, None);
+    settings.set(Settings::dynamic_setter(".timeout", Some(3u16)).unwrap()).unwrap();
+    assert_eq!(settings.timeout, Some(3));
+    settings.set(Settings::dynamic_setter(".timeout", None::<u16>).unwrap()).unwrap();
+    assert_eq!(settings.timeout, None);
+    settings.set(Settings::dynamic_setter_numeric(".timeout", 4.0).unwrap()).unwrap();
+    assert_eq!(settings.timeout, Some(4));
+    settings.set(Settings::dynamic_setter_numeric(".calibration::Some.offset", 0.5).unwrap()).unwrap();
+    assert_eq!(settings.calibration, Some(Calibration { offset: 0.5, scale: 0.0 }));
+
+    // a wrongly typed payload is rejected
+    assert!(Settings::dynamic_setter(".timeout", Some(3u32)).is_none());
+    assert!(Settings::dynamic_setter(".timeout::None", 0u16).is_none());
+
+    let path = ".timeout::Some";
+    let getter = Settings::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = settings.get(getter).unwrap();
+    assert_eq!(value.parse_value::<u16>(path), Some(4));
+    assert_eq!(value.as_float(), Some(4.0));
+
+    let mut buf = [0; 2];
+    let len = value.dehydrate(&mut buf).unwrap();
+    let (rehydrated_v, length) = <Settings as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert_eq!(len, length);
+    assert!(rehydrated_v == value);
+
+    let getter = Settings::dynamic_getter(".timeoutVARIANT").unwrap();
+    assert_eq!(format!("{}", getter), ".timeoutVARIANT");
+    let value = settings.get(getter).unwrap();
+    let mut buf = [0; 1];
+    value.dehydrate(&mut buf).unwrap();
+    assert_eq!(buf, [1]);
+    let (rehydrated_v, _) = <Settings as RemoteGet>::hydrate(getter, &buf).unwrap();
+    assert!(rehydrated_v == value);
+    assert_eq!(<Settings as RemoteGet>::hydrate(getter, &[2]).err(), Some(Error::InvalidEncoding { depth: 1 }));
+
+    assert_eq!(Some(FieldsType::Fields(&["::Some", "::None", "VARIANT"])), <Settings as RemoteGet>::GetterType::get_fields(".timeout"));
+    assert_eq!(Some(FieldsType::Terminal), <Settings as RemoteGet>::GetterType::get_fields(".timeout::Some"));
+    assert_eq!(Some(FieldsType::Fields(&[".offset", ".scale"])), <Settings as RemoteGet>::GetterType::get_fields(".calibration::Some"));
+}
+
+/// neither `Default` nor `Copy`
+#[derive(RemoteSetter, RemoteGetter, Debug, PartialEq)]
+pub struct Port {
+    baud: u32,
+}
+
+#[derive(RemoteSetter, RemoteGetter, Clone, Copy, Debug, PartialEq)]
+#[remote(replace)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Link {
+    port: Option<Port>,
+    parity: Option<Parity>,
+    name: Option<char>,
+}
+
+#[test]
+fn test_option_no_default() {
+    let mut link = Link { port: Some(Port { baud: 9600 }), parity: None, name: None };
+
+    link.set(setter!(Link.port::Some.baud = 115200)).unwrap();
+    assert_eq!(link.port, Some(Port { baud: 115200 }));
+    link.set(setter!(Link.port::None)).unwrap();
+    assert_eq!(link.port, None);
+    assert_eq!(link.set(setter!(Link.port::Some.baud = 9600)), Err(Error::WrongVariant { depth: 1 }));
+
+    // whole values are inserted, including replacing enums
+    link.set(setter!(Link.parity = Some(ParitySetter::setter_from(Parity::Odd)))).unwrap();
+    assert_eq!(link.parity, Some(Parity::Odd));
+    link.set(setter!(Link.name = Some('a'))).unwrap();
+    assert_eq!(link.name, Some('a'));
+}
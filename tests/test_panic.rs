@@ -0,0 +1,118 @@
+use remote_obj::prelude::*;
+use bincode::{Encode, Decode};
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(derive(Encode, Decode))]
+pub struct Inner {
+    a: i8,
+    b: u16,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(derive(Encode, Decode))]
+pub enum TestEnum {
+    A,
+    B(Inner),
+    C,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(derive(Encode, Decode))]
+pub struct Test {
+    a: f32,
+    b: Inner,
+    c: TestEnum,
+    d: [Inner; 2],
+}
+
+fn new_test() -> Test {
+    Test {
+        a: 0.0,
+        b: Inner { a: 0, b: 0 },
+        c: TestEnum::B(Inner { a: 0, b: 0 }),
+        d: [Inner { a: 0, b: 0 }, Inner { a: 0, b: 0 }],
+    }
+}
+
+#[test]
+fn test_none() {
+    let mut test = new_test();
+
+    let getter = <Test as RemoteGet>::GetterType::default();
+    assert_eq!(test.get(getter).err(), Some(Error::EmptyPath { depth: 0 }));
+    assert_eq!(<Test as RemoteGet>::hydrate(getter, &[0; 8]).err(), Some(Error::EmptyPath { depth: 0 }));
+    assert_eq!(format!("{}", getter), "");
+
+    let getter = TestGetter::default().make_b(|x| x);
+    assert_eq!(test.get(getter).err(), Some(Error::EmptyPath { depth: 1 }));
+
+    let getter = TestGetter::default().make_c(|x| x);
+    assert_eq!(test.get(getter).err(), Some(Error::EmptyPath { depth: 1 }));
+    assert_eq!(<Test as RemoteGet>::hydrate(getter, &[0; 8]).err(), Some(Error::EmptyPath { depth: 1 }));
+
+    let setter = <Test as RemoteSet>::SetterType::default();
+    assert_eq!(test.set(setter), Err(Error::EmptyPath { depth: 0 }));
+    assert_eq!(format!("{}", setter), "");
+
+    let setter = <TestEnum as RemoteSet>::SetterType::default();
+    assert_eq!(test.c.set(setter), Err(Error::EmptyPath { depth: 0 }));
+    assert_eq!(format!("{}", setter), "");
+}
+
+#[test]
+fn test_variant_roundtrip() {
+    let test = new_test();
+
+    let g = getter!(Test.c.var);
+    let v = test.get(g).unwrap();
+    let mut buf = [0; 4];
+    assert_eq!(v.dehydrate(&mut buf), Some(1));
+    let (rehydrated_v, length) = <Test as RemoteGet>::hydrate(g, &buf).unwrap();
+    assert_eq!(length, 1);
+    assert!(rehydrated_v == v);
+
+    assert_eq!(v.dehydrate(&mut []), None);
+    assert_eq!(<Test as RemoteGet>::hydrate(g, &[]).err(), Some(Error::BufferTooShort { depth: 1 }));
+    assert_eq!(<Test as RemoteGet>::hydrate(g, &[3]).err(), Some(Error::InvalidEncoding { depth: 1 }));
+}
+
+#[test]
+fn test_bad_paths() {
+    for path in ["", ".", ".d", ".b.", ".b.c", ".c::", ".c::D", "[", "]", "][", ".a[0]", ".c::B[1]", ".d[2].a", ".d[-1].a", ".d[].a", ".d[0"] {
+        assert!(Test::dynamic_getter(path).is_none(), "{}", path);
+        assert!(Test::dynamic_setter_numeric(path, 1.0).is_none(), "{}", path);
+    }
+
+    // unit variants cannot be set with a value
+    assert!(Test::dynamic_setter::<u32>(".c::A", 1).is_none());
+
+    // size mismatches are rejected instead of panicking
+    assert!(Test::dynamic_setter::<u64>(".b.a", 1).is_none());
+    let v = new_test().get(getter!(Test.b.a)).unwrap();
+    assert!(v.parse_value::<u64>(".b.a").is_none());
+}
+
+#[test]
+fn test_decoded() {
+    let config = bincode::config::standard();
+    let mut buf = [0u8; 8];
+
+    for a in 0..=255u8 {
+        for b in 0..=255u8 {
+            buf[0] = a;
+            buf[1] = b;
+            buf[2] = b.wrapping_mul(7);
+            if let Ok((g, _)) = bincode::decode_from_slice::<<Test as RemoteGet>::GetterType, _>(&buf, config) {
+                let _ = format!("{}", g);
+                let _ = <Test as RemoteGet>::hydrate(g, &buf[2..]);
+                if let Ok(v) = new_test().get(g) {
+                    let _ = v.dehydrate(&mut buf[2..]);
+                }
+            }
+            if let Ok((s, _)) = bincode::decode_from_slice::<<Test as RemoteSet>::SetterType, _>(&buf, config) {
+                let _ = format!("{}", s);
+                let _ = new_test().set(s);
+            }
+        }
+    }
+}
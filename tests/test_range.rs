@@ -0,0 +1,120 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Limits {
+    #[remote(range(min = "-10", max = 10))]
+    lo: i16,
+    #[remote(range(min = 0.0, max = 1.0, clamp))]
+    duty: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Mode {
+    Off,
+    Pid {
+        #[remote(range(min = 0.0, max = 100.0))]
+        kp: f32,
+    },
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Motor {
+    #[remote(range(min = 0, max = 200))]
+    speed: u8,
+    limits: Limits,
+    channels: [Limits; 2],
+    mode: Mode,
+}
+
+#[derive(RemoteSetter, RemoteGetter, Clone, Copy, PartialEq, Debug)]
+#[remote(replace)]
+pub enum Drive {
+    Stop,
+    Run {
+        #[remote(range(min = 0, max = 10))]
+        speed: u8,
+        #[remote(range(min = 0.0, max = 1.0, clamp))]
+        duty: f32,
+    },
+    Level(#[remote(range(min = 0.0, max = 1.0))] f32),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Cfg {
+    drive: Drive,
+}
+
+#[test]
+fn test_range() {
+    let mut motor = Motor {
+        speed: 0,
+        limits: Limits { lo: 0, duty: 0.0 },
+        channels: [Limits { lo: 0, duty: 0.0 }, Limits { lo: 0, duty: 0.0 }],
+        mode: Mode::Pid { kp: 1.0 },
+    };
+
+    motor.set(setter!(Motor.speed = 200)).unwrap();
+    assert_eq!(motor.speed, 200);
+    assert_eq!(motor.set(setter!(Motor.speed = 201)), Err(Error::OutOfRange { depth: 0 }));
+    assert_eq!(motor.speed, 200);
+
+    motor.set(setter!(Motor.limits.lo = -10)).unwrap();
+    assert_eq!(motor.set(setter!(Motor.limits.lo = -11)), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(motor.limits.lo, -10);
+    assert_eq!(motor.set(Motor::dynamic_setter_numeric(".channels[1].lo", 11.0).unwrap()), Err(Error::OutOfRange { depth: 2 }));
+
+    // clamped instead of rejected
+    motor.set(setter!(Motor.limits.duty = 1.5)).unwrap();
+    assert_eq!(motor.limits.duty, 1.0);
+    motor.set(setter!(Motor.limits.duty = -0.5)).unwrap();
+    assert_eq!(motor.limits.duty, 0.0);
+    assert_eq!(motor.set(setter!(Motor.limits.duty = f32::NAN)), Err(Error::OutOfRange { depth: 1 }));
+
+    motor.set(setter!(Motor.mode::Pid.kp = 50.0)).unwrap();
+    assert_eq!(motor.set(setter!(Motor.mode::Pid.kp = 150.0)), Err(Error::OutOfRange { depth: 2 }));
+    assert!(matches!(motor.mode, Mode::Pid { kp } if kp == 50.0));
+}
+
+#[test]
+fn test_range_replace() {
+    let mut cfg = Cfg { drive: Drive::Stop };
+
+    assert_eq!(cfg.set(setter!(Cfg.drive = Drive::Run { speed: 200, duty: 0.5 })), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(cfg.drive, Drive::Stop);
+    cfg.set(setter!(Cfg.drive = Drive::Run { speed: 10, duty: 2.0 })).unwrap();
+    assert_eq!(cfg.drive, Drive::Run { speed: 10, duty: 1.0 });
+    assert_eq!(cfg.set(setter!(Cfg.drive::Run.speed = 200)), Err(Error::OutOfRange { depth: 2 }));
+    cfg.set(setter!(Cfg.drive = Drive::Stop)).unwrap();
+}
+
+#[test]
+fn test_range_newtype() {
+    let mut cfg = Cfg { drive: Drive::Level(0.5) };
+
+    cfg.set(setter!(Cfg.drive::Level = 1.0)).unwrap();
+    assert_eq!(cfg.set(setter!(Cfg.drive::Level = 5.0)), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(cfg.set(Cfg::dynamic_setter_numeric(".drive::Level", -1.0).unwrap()), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(cfg.drive, Drive::Level(1.0));
+
+    assert_eq!(cfg.set(setter!(Cfg.drive = Drive::Level(7.0))), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(cfg.drive, Drive::Level(1.0));
+    cfg.set(setter!(Cfg.drive = Drive::Level(0.25))).unwrap();
+    assert_eq!(cfg.drive, Drive::Level(0.25));
+
+    let range = <Cfg as RemoteGet>::get_metadata(".drive::Level").unwrap().range;
+    assert_eq!(range, Some(ValueRange { min: 0.0, max: 1.0 }));
+}
+
+#[test]
+fn test_range_metadata() {
+    let get_metadata = <Motor as RemoteGet>::get_metadata;
+
+    assert_eq!(get_metadata(".speed").unwrap().range, Some(ValueRange { min: 0.0, max: 200.0 }));
+    assert_eq!(get_metadata(".limits.lo").unwrap().range, Some(ValueRange { min: -10.0, max: 10.0 }));
+    assert_eq!(get_metadata(".channels[1].duty").unwrap().range, Some(ValueRange { min: 0.0, max: 1.0 }));
+    assert_eq!(get_metadata(".mode::Pid.kp").unwrap().range, Some(ValueRange { min: 0.0, max: 100.0 }));
+    assert_eq!(get_metadata(".limits").unwrap().range, None);
+    assert_eq!(get_metadata("").unwrap().range, None);
+    assert_eq!(get_metadata(".limits.hi"), None);
+    assert_eq!(get_metadata(".channels[2].lo"), None);
+}
@@ -0,0 +1,119 @@
+use remote_obj::prelude::*;
+
+/// stands in for a vendor crate, whose types can't be annotated
+mod hal {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Polarity {
+        High,
+        Low,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct PwmConfig {
+        pub duty: f32,
+        pub frequency: u32,
+        pub polarity: Polarity,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct Channel(pub u8, pub PwmConfig);
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(remote = "hal::Polarity")]
+pub enum PolarityDef {
+    High,
+    Low,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(remote = "hal::PwmConfig")]
+pub struct PwmConfigDef {
+    #[remote(range(min = 0.0, max = 1.0))]
+    duty: f32,
+    #[remote(read_only)]
+    frequency: u32,
+    #[remote(with = "PolarityDef")]
+    polarity: hal::Polarity,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+#[remote(remote = "hal::Channel")]
+pub struct ChannelDef(u8, #[remote(with = "PwmConfigDef")] hal::PwmConfig);
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Motor {
+    #[remote(with = "PwmConfigDef")]
+    pwm: hal::PwmConfig,
+    #[remote(with = "ChannelDef")]
+    aux: hal::Channel,
+    enabled: bool,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Output {
+    Off,
+    Pwm(#[remote(with = "PwmConfigDef")] hal::PwmConfig),
+}
+
+#[test]
+fn remote_get() {
+    let pwm = hal::PwmConfig { duty: 0.5, frequency: 20_000, polarity: hal::Polarity::High };
+    let m = Motor { pwm, aux: hal::Channel(3, pwm), enabled: true };
+    assert_eq!(m.get(getter!(Motor.pwm.duty)).unwrap().pwm().duty(), 0.5);
+    assert_eq!(m.get(getter!(Motor.pwm.frequency)).unwrap().pwm().frequency(), 20_000);
+    assert_eq!(m.get(getter!(Motor.aux.1.frequency)).unwrap().aux()._1().frequency(), 20_000);
+
+    let getter = <Motor as RemoteGet>::GetterType::parse_getter(".pwm.polarityVARIANT").unwrap();
+    assert!(getter == getter!(Motor.pwm.polarity.var));
+    assert!(matches!(m.get(getter).unwrap().pwm().polarity(), PolarityDefValue::High));
+
+    assert_eq!(
+        Some(FieldsType::Fields(&[".duty", ".frequency", ".polarity"])),
+        <Motor as RemoteGet>::GetterType::get_fields(".pwm")
+    );
+    assert_eq!(Some(FieldsType::Terminal), m.dynamic_fields(".aux.1.duty", &mut |_| {}));
+    assert_eq!(Some(1.0), Motor::get_metadata(".pwm.duty").unwrap().range.map(|range| range.max));
+}
+
+#[test]
+fn remote_set() {
+    let pwm = hal::PwmConfig { duty: 0.5, frequency: 20_000, polarity: hal::Polarity::High };
+    let mut m = Motor { pwm, aux: hal::Channel(3, pwm), enabled: true };
+    m.set(setter!(Motor.pwm.duty = 0.25)).unwrap();
+    m.set(Motor::dynamic_setter(".pwm.polarity::Low", ()).unwrap()).unwrap();
+    m.set(Motor::dynamic_setter_numeric(".aux.0", 7.0).unwrap()).unwrap();
+    m.set(setter!(Motor.aux.1.duty = 0.75)).unwrap();
+    assert_eq!(m.pwm, hal::PwmConfig { duty: 0.25, frequency: 20_000, polarity: hal::Polarity::Low });
+    assert_eq!(m.aux, hal::Channel(7, hal::PwmConfig { duty: 0.75, ..pwm }));
+
+    // checks of the mirror apply to the foreign type, and a failed write leaves it untouched
+    assert_eq!(m.set(setter!(Motor.pwm.duty = 2.0)), Err(Error::OutOfRange { depth: 1 }));
+    assert_eq!(m.pwm.duty, 0.25);
+    assert!(Motor::dynamic_setter(".pwm.frequency", 1u32).is_none());
+
+    let mut out = Output::Pwm(pwm);
+    out.set(Output::dynamic_setter("::Pwm.duty", 0.1f32).unwrap()).unwrap();
+    assert!(matches!(out, Output::Pwm(hal::PwmConfig { duty, .. }) if duty == 0.1));
+}
+
+#[test]
+fn remote_reset() {
+    let pwm = hal::PwmConfig { duty: 0.5, frequency: 20_000, polarity: hal::Polarity::High };
+    let mut m = Motor { pwm, aux: hal::Channel(3, pwm), enabled: true };
+    m.set(setter!(Motor.pwm.polarity::Low)).unwrap();
+
+    let mut paths = Vec::new();
+    m.diff_default(&FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, [".pwm.duty", ".aux.0", ".aux.1.duty", ".enabled"]);
+
+    m.set(setter!(Motor.pwm reset)).unwrap();
+    assert_eq!(m.pwm.duty, 0.0);
+    // read-only fields of the mirror are not reset
+    assert_eq!(m.pwm.frequency, 20_000);
+    assert_eq!(m.pwm.polarity, hal::Polarity::Low);
+
+    paths.clear();
+    Output::Pwm(pwm).diff(&Output::Pwm(m.pwm), &FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, ["::Pwm.duty", "::Pwm.polarity"]);
+}
@@ -0,0 +1,67 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter, Clone, Copy)]
+#[remote(rename_all = "camelCase")]
+pub struct Gains {
+    kp_gain: f32,
+    #[remote(rename = "integral")]
+    ki_gain: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter, Clone, Copy)]
+#[remote(rename_all = "snake_case", replace)]
+pub enum Mode {
+    Off,
+    ClosedLoop(Gains),
+    #[remote(rename = "raw")]
+    OpenLoop { duty: f32 },
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Motor {
+    #[remote(rename = "m")]
+    mode: Mode,
+    speed: u8,
+}
+
+#[test]
+fn test_rename() {
+    let mut motor = Motor {
+        mode: Mode::ClosedLoop(Gains { kp_gain: 1.0, ki_gain: 0.0 }),
+        speed: 0,
+    };
+
+    // the typed macros keep using the Rust names
+    motor.set(setter!(Motor.mode::ClosedLoop.ki_gain = 0.5)).unwrap();
+    let getter = getter!(Motor.mode::ClosedLoop.ki_gain);
+    let value = motor.get(getter).unwrap();
+    assert_eq!(value.mode().ClosedLoop().ki_gain(), 0.5);
+
+    // while paths use the renamed ones
+    let path = ".m::closed_loop.integral";
+    assert_eq!(format!("{}", getter), path);
+    assert!(Motor::dynamic_getter(path).unwrap() == getter);
+    assert_eq!(value.parse_value::<f32>(path), Some(0.5));
+    assert!(Motor::dynamic_getter(".mode::ClosedLoop.ki_gain").is_none());
+
+    motor.set(Motor::dynamic_setter_numeric(".m::closed_loop.kpGain", 2.0).unwrap()).unwrap();
+    let setter = setter!(Motor.mode::ClosedLoop.kp_gain = 2.0);
+    assert!(format!("{}", setter).starts_with(".m::closed_loop.kpGain"));
+    assert!(Motor::dynamic_setter_numeric(".m::closed_loop.kp_gain", 2.0).is_none());
+
+    motor.set(Motor::dynamic_setter(".m::off", ()).unwrap()).unwrap();
+    assert!(matches!(motor.mode, Mode::Off));
+    assert_eq!(format!("{}", setter!(Motor.mode::Off)), ".m = ::off");
+
+    motor.set(setter!(Motor.mode = Mode::OpenLoop { duty: 0.1 })).unwrap();
+    assert_eq!(format!("{}", setter!(Motor.mode = Mode::OpenLoop { duty: 0.1 })), ".m = ::raw(..)");
+    motor.set(Motor::dynamic_setter_numeric(".m::raw.duty", 0.2).unwrap()).unwrap();
+    assert_eq!(motor.get(getter!(Motor.mode::OpenLoop.duty)).unwrap().mode().OpenLoop().duty(), 0.2);
+    motor.set(Motor::dynamic_setter_numeric(".speed", 3.0).unwrap()).unwrap();
+    assert_eq!(motor.speed, 3);
+
+    let getter_fields = <Motor as RemoteGet>::GetterType::get_fields;
+    assert_eq!(Some(FieldsType::Fields(&[".m", ".speed"])), getter_fields(""));
+    assert_eq!(Some(FieldsType::Fields(&["::closed_loop", "::raw", "VARIANT"])), getter_fields(".m"));
+    assert_eq!(Some(FieldsType::Fields(&[".kpGain", ".integral"])), getter_fields(".m::closed_loop"));
+}
@@ -0,0 +1,372 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Gains {
+    #[remote(default = 2.0)]
+    kp: f32,
+    ki: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter, Default)]
+pub enum Mode {
+    #[default]
+    Idle,
+    Fast,
+    Run {
+        #[remote(default = 10.0)]
+        speed: f32,
+        ramp: u8,
+    },
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Config {
+    #[remote(default = 1.5)]
+    a: f32,
+    b: u8,
+    d: Gains,
+    #[remote(default = "Mode::Idle")]
+    mode: Mode,
+    arr: [i16; 3],
+    opt: Option<u8>,
+    #[remote(default = "-1")]
+    offset: i32,
+    #[remote(read_only)]
+    serial: u32,
+}
+
+fn changed(c: &Config) -> Vec<String> {
+    let mut paths = Vec::new();
+    c.diff_default(&FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    paths
+}
+
+#[test]
+fn reset_leaf() {
+    let mut c = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    c.set(setter!(Config.a reset)).unwrap();
+    c.set(setter!(Config.b reset)).unwrap();
+    c.set(setter!(Config.offset reset)).unwrap();
+    c.set(setter!(Config.d.ki reset)).unwrap();
+    assert_eq!(c.a, 1.5);
+    assert_eq!(c.b, 0);
+    assert_eq!(c.offset, -1);
+    assert_eq!(c.d.ki, 0.0);
+    assert_eq!(c.d.kp, 5.0);
+
+    c.set(setter!(Config.arr reset)).unwrap();
+    c.set(setter!(Config.opt reset)).unwrap();
+    assert_eq!(c.arr, [0, 0, 0]);
+    assert_eq!(c.opt, None);
+}
+
+#[test]
+fn reset_nested() {
+    let mut c = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    c.set(setter!(Config.d reset)).unwrap();
+    assert_eq!(c.d.kp, 2.0);
+    assert_eq!(c.d.ki, 0.0);
+    assert_eq!(c.a, 3.0);
+
+    c.set(setter!(Config.mode::Run.speed reset)).unwrap();
+    assert!(matches!(c.mode, Mode::Run { speed, ramp: 4 } if speed == 10.0));
+    c.mode = Mode::Run { speed: 2.0, ramp: 4 };
+    c.set(setter!(Config.mode::Run reset)).unwrap();
+    assert!(matches!(c.mode, Mode::Run { speed, ramp: 0 } if speed == 10.0));
+    c.set(setter!(Config.mode reset)).unwrap();
+    assert!(matches!(c.mode, Mode::Idle));
+
+    assert_eq!(c.set(setter!(Config.mode::Run reset)), Err(Error::WrongVariant { depth: 1 }));
+}
+
+#[test]
+fn reset_whole() {
+    let mut c = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    c.set(setter!(Config reset)).unwrap();
+    assert_eq!(c.a, 1.5);
+    assert_eq!(c.b, 0);
+    assert_eq!(c.d.kp, 2.0);
+    assert!(matches!(c.mode, Mode::Idle));
+    assert_eq!(c.arr, [0, 0, 0]);
+    assert_eq!(c.opt, None);
+    assert_eq!(c.offset, -1);
+    // read-only fields are not written
+    assert_eq!(c.serial, 1234);
+}
+
+#[test]
+fn reset_getter() {
+    let mut c = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    c.reset(getter!(Config.d.kp)).unwrap();
+    assert_eq!(c.d.kp, 2.0);
+    assert_eq!(c.d.ki, 1.0);
+    c.reset(getter!(Config.mode::Run.ramp)).unwrap();
+    assert!(matches!(c.mode, Mode::Run { ramp: 0, .. }));
+    // the empty getter addresses the whole object
+    c.reset(ConfigGetter::default()).unwrap();
+    assert_eq!(c.a, 1.5);
+    assert_eq!(c.d.ki, 0.0);
+    assert!(matches!(c.mode, Mode::Idle));
+
+    // only fields and whole values can be reset
+    assert_eq!(c.reset(getter!(Config.arr[1])), Err(Error::NotResettable { depth: 0 }));
+    assert_eq!(c.reset(getter!(Config.serial)), Err(Error::NotResettable { depth: 0 }));
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct FilterStage {
+    #[remote(default = 0.5)]
+    coefficient_of_the_feedback_path: f32,
+    gain: f32,
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Amplifier {
+    stages_of_the_filter_cascade_in_this_channel: [FilterStage; 2],
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Rack {
+    channels_of_the_left_amplifier_in_the_rack: [Option<Amplifier>; 2],
+}
+
+#[test]
+fn reset_getter_long_path() {
+    let mut r = Rack {
+        channels_of_the_left_amplifier_in_the_rack: [None, Some(Amplifier {
+            stages_of_the_filter_cascade_in_this_channel: [
+                FilterStage { coefficient_of_the_feedback_path: 1.0, gain: 1.0 },
+                FilterStage { coefficient_of_the_feedback_path: 2.0, gain: 2.0 },
+            ],
+        })],
+    };
+    let getter = getter!(Rack.channels_of_the_left_amplifier_in_the_rack[1]::Some.stages_of_the_filter_cascade_in_this_channel[1].coefficient_of_the_feedback_path);
+    // resetting doesn't go through the path as text, so its length doesn't matter
+    assert!(format!("{} reset", getter).len() > 128);
+    r.reset(getter).unwrap();
+    let stages = &r.channels_of_the_left_amplifier_in_the_rack[1].as_ref().unwrap().stages_of_the_filter_cascade_in_this_channel;
+    assert_eq!(stages[1].coefficient_of_the_feedback_path, 0.5);
+    assert_eq!(stages[1].gain, 2.0);
+
+    r.reset(getter!(Rack.channels_of_the_left_amplifier_in_the_rack[1]::Some.stages_of_the_filter_cascade_in_this_channel[0].gain)).unwrap();
+    let stages = &r.channels_of_the_left_amplifier_in_the_rack[1].as_ref().unwrap().stages_of_the_filter_cascade_in_this_channel;
+    assert_eq!(stages[0].coefficient_of_the_feedback_path, 1.0);
+    assert_eq!(stages[0].gain, 0.0);
+
+    let getter = getter!(Rack.channels_of_the_left_amplifier_in_the_rack[0]::Some.stages_of_the_filter_cascade_in_this_channel[0].gain);
+    assert_eq!(r.reset(getter), Err(Error::WrongVariant { depth: 2 }));
+    let getter = Rack::dynamic_getter(".channels_of_the_left_amplifier_in_the_rack[0]VARIANT");
+    assert_eq!(r.reset(getter.unwrap()), Err(Error::NotResettable { depth: 0 }));
+}
+
+#[test]
+fn reset_dynamic() {
+    let mut c = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    assert_eq!(format!("{}", setter!(Config.d.kp reset)), ".d.kp reset");
+    assert_eq!(format!("{}", setter!(Config.mode::Run reset)), ".mode::Run reset");
+    assert_eq!(format!("{}", setter!(Config reset)), " reset");
+
+    c.set(Config::dynamic_setter(".d reset", ()).unwrap()).unwrap();
+    assert_eq!(c.d.kp, 2.0);
+    assert!(Config::dynamic_setter(".d reset", 1.0f32).is_none());
+    assert!(Config::dynamic_setter(".x reset", ()).is_none());
+    assert_eq!(format!("{}", Config::dynamic_setter(".mode::Run.speed reset", ()).unwrap()), ".mode::Run.speed reset");
+}
+
+#[test]
+fn diff_default() {
+    let mut c = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    assert_eq!(changed(&c), [".a", ".b", ".d.kp", ".d.ki", ".mode", ".arr[0]", ".arr[1]", ".arr[2]", ".opt", ".offset"]);
+
+    c.set(setter!(Config reset)).unwrap();
+    assert!(changed(&c).is_empty());
+
+    c.set(setter!(Config.arr[1] = 5)).unwrap();
+    c.set(setter!(Config.d.kp = 1.0)).unwrap();
+    c.mode = Mode::Run { speed: 10.0, ramp: 0 };
+    assert_eq!(changed(&c), [".d.kp", ".mode", ".arr[1]"]);
+
+    // enums with a `#[default]` variant differ from it as a whole
+    let mut mode = Vec::new();
+    c.mode.diff_default(&FieldPath::root().field(".mode"), &mut |path| mode.push(format!("{}", path)));
+    assert_eq!(mode, [".mode"]);
+    mode.clear();
+    Mode::Idle.diff_default(&FieldPath::root().field(".mode"), &mut |path| mode.push(format!("{}", path)));
+    assert!(mode.is_empty());
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub enum Gear {
+    Low,
+    High { ratio: f32 },
+    Manual(Gains),
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Drive {
+    mode: Mode,
+    gear: Gear,
+}
+
+#[test]
+fn reset_enum_default() {
+    let mut d = Drive { mode: Mode::Fast, gear: Gear::High { ratio: 2.0 } };
+    let mut paths = Vec::new();
+    d.diff_default(&FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, [".mode", ".gear::High.ratio"]);
+
+    d.set(setter!(Drive.mode reset)).unwrap();
+    assert!(matches!(d.mode, Mode::Idle));
+    d.mode = Mode::Run { speed: 2.0, ramp: 4 };
+    d.set(setter!(Drive reset)).unwrap();
+    assert!(matches!(d.mode, Mode::Idle));
+    // enums without a `#[default]` variant reset the fields of the active variant
+    assert!(matches!(d.gear, Gear::High { ratio } if ratio == 0.0));
+
+    paths.clear();
+    d.diff_default(&FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert!(paths.is_empty());
+
+    // a unit variant has no fields to reset
+    d.gear = Gear::Low;
+    d.set(setter!(Drive.gear reset)).unwrap();
+    assert!(matches!(d.gear, Gear::Low));
+    d.mode = Mode::Fast;
+    assert_eq!(d.reset(getter!(Drive.mode::Run.speed)), Err(Error::WrongVariant { depth: 1 }));
+    assert!(matches!(d.mode, Mode::Fast));
+
+    // newtype variants reset the value they hold, with its defaults
+    d.gear = Gear::Manual(Gains { kp: 5.0, ki: 1.0 });
+    d.reset(getter!(Drive.gear::Manual.ki)).unwrap();
+    assert!(matches!(d.gear, Gear::Manual(Gains { kp, ki }) if kp == 5.0 && ki == 0.0));
+    d.set(setter!(Drive.gear::Manual reset)).unwrap();
+    assert!(matches!(d.gear, Gear::Manual(Gains { kp, ki: 0.0 }) if kp == 2.0));
+    d.gear = Gear::Manual(Gains { kp: 5.0, ki: 1.0 });
+    d.set(setter!(Drive.gear reset)).unwrap();
+    assert!(matches!(d.gear, Gear::Manual(Gains { kp, ki: 0.0 }) if kp == 2.0));
+    d.gear = Gear::Low;
+    assert_eq!(d.reset(getter!(Drive.gear::Manual.kp)), Err(Error::WrongVariant { depth: 1 }));
+}
+
+#[test]
+fn diff() {
+    let a = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    let mut b = Config {
+        a: 3.0,
+        b: 7,
+        d: Gains { kp: 5.0, ki: 1.0 },
+        mode: Mode::Run { speed: 2.0, ramp: 4 },
+        arr: [1, 2, 3],
+        opt: Some(9),
+        offset: 4,
+        serial: 1234,
+    };
+    b.d.ki = 2.0;
+    b.arr[2] = 0;
+    b.mode = Mode::Run { speed: 2.0, ramp: 5 };
+
+    let mut paths = Vec::new();
+    a.diff(&b, &FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, [".d.ki", ".mode::Run.ramp", ".arr[2]"]);
+
+    b.mode = Mode::Idle;
+    paths.clear();
+    a.diff(&b, &FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, [".d.ki", ".mode", ".arr[2]"]);
+}
+
+/// written by hand, without resetting or diffing
+pub struct Counter(u32);
+
+impl RemoteSet for Counter {
+    type SetterType = u32;
+
+    fn set(&mut self, x: u32) -> Result<(), Error> {
+        self.0 += x;
+        Ok(())
+    }
+}
+
+#[derive(RemoteSetter)]
+pub struct Meter {
+    count: Counter,
+    scale: f32,
+}
+
+#[test]
+fn reset_manual() {
+    let mut m = Meter { count: Counter(0), scale: 2.0 };
+    m.set(setter!(Meter.count = 3)).unwrap();
+    assert_eq!(m.count.0, 3);
+
+    assert_eq!(m.set(setter!(Meter.count reset)), Err(Error::NotResettable { depth: 1 }));
+    assert_eq!(m.set(setter!(Meter reset)), Err(Error::NotResettable { depth: 1 }));
+    assert_eq!(m.scale, 0.0);
+
+    let mut paths = Vec::new();
+    m.diff_default(&FieldPath::root(), &mut |path| paths.push(format!("{}", path)));
+    assert_eq!(paths, [".count"]);
+}
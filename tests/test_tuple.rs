@@ -0,0 +1,94 @@
+use remote_obj::prelude::*;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Gain(f32);
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Pins(u8, #[remote(read_only)] u8, u8);
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Unit;
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Nested(Gain, Pins);
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Test {
+    a: Gain,
+    b: Nested,
+    c: Unit,
+}
+
+#[test]
+fn test_tuple() {
+    let mut test = Test {
+        a: Gain(1.0),
+        b: Nested(Gain(2.0), Pins(1, 2, 3)),
+        c: Unit,
+    };
+
+    test.set(setter!(Test.a.0 = 3.0)).unwrap();
+    let v = test.get(getter!(Test.a.0)).unwrap();
+    assert_eq!(v.a()._0(), 3.0);
+
+    // `.1.2` is lexed as a float literal
+    test.set(setter!(Test.b.1.2 = 4)).unwrap();
+    let v = test.get(getter!(Test.b.1.2)).unwrap();
+    assert_eq!(v.b()._1()._2(), 4);
+
+    let v = test.get(getter!(Test.b.1.1)).unwrap();
+    assert_eq!(v.b()._1()._1(), 2);
+
+    let path = ".b.0.0";
+    let setter = Test::dynamic_setter::<f32>(path, 5.0).unwrap();
+    test.set(setter).unwrap();
+    let getter = Test::dynamic_getter(path).unwrap();
+    assert_eq!(format!("{}", getter), path);
+    let value = test.get(getter).unwrap();
+    assert_eq!(value.parse_value::<f32>(path), Some(5.0));
+
+    // read only tuple field
+    assert!(Test::dynamic_setter::<u8>(".b.1.1", 1).is_none());
+    assert!(Test::dynamic_getter(".b.1.1").is_some());
+
+    assert_eq!(Some(FieldsType::Fields(&[".a", ".b", ".c"])), <Test as RemoteGet>::GetterType::get_fields(""));
+    assert_eq!(Some(FieldsType::Fields(&[".0", ".1"])), <Test as RemoteGet>::GetterType::get_fields(".b"));
+    assert_eq!(Some(FieldsType::Fields(&[".0", ".1", ".2"])), <Test as RemoteGet>::GetterType::get_fields(".b.1"));
+    assert_eq!(Some(FieldsType::Terminal), <Test as RemoteGet>::GetterType::get_fields(".b.1.2"));
+    assert_eq!(Some(FieldsType::Fields(&[])), <Test as RemoteGet>::GetterType::get_fields(".c"));
+}
+
+#[test]
+fn test_unit() {
+    let mut unit = Unit;
+    assert!(Unit::dynamic_getter("").is_none());
+    assert!(Unit::dynamic_getter(".0").is_none());
+    assert_eq!(unit.get(<Unit as RemoteGet>::GetterType::default()).err(), Some(Error::EmptyPath { depth: 0 }));
+    assert_eq!(unit.set(<Unit as RemoteSet>::SetterType::default()), Err(Error::EmptyPath { depth: 0 }));
+}
+
+#[derive(RemoteSetter, RemoteGetter)]
+pub struct Wide(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u16);
+
+#[test]
+fn test_wide_tuple() {
+    let mut wide = Wide(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+
+    // `.1` must not swallow `.10` and `.11`
+    let getter = <Wide as RemoteGet>::GetterType::parse_getter(".11").unwrap();
+    assert!(getter == getter!(Wide.11));
+    assert_eq!(format!("{}", getter), ".11");
+    assert_eq!(wide.get(getter).unwrap()._11(), 11);
+    assert_eq!(wide.get(getter!(Wide.10)).unwrap()._10(), 10);
+    assert_eq!(wide.get(getter!(Wide.1)).unwrap()._1(), 1);
+
+    wide.set(Wide::dynamic_setter(".10", 20u8).unwrap()).unwrap();
+    wide.set(Wide::dynamic_setter_numeric(".11", 21.0).unwrap()).unwrap();
+    wide.set(setter!(Wide.1 = 30)).unwrap();
+    assert_eq!((wide.1, wide.10, wide.11), (30, 20, 21));
+
+    assert_eq!(Some(FieldsType::Terminal), <Wide as RemoteGet>::GetterType::get_fields(".10"));
+    assert_eq!(Some(FieldsType::Terminal), wide.dynamic_fields(".11", &mut |_| {}));
+    assert!(<Wide as RemoteGet>::GetterType::parse_getter(".12").is_none());
+    assert!(<Wide as RemoteGet>::GetterType::parse_getter(".1x").is_none());
+}